@@ -0,0 +1,24 @@
+//! Benches every part of every solution in `src/bin` and stores the results like criterion, see
+//! `advent_of_code::template::commands::bench`.
+use advent_of_code::template::commands::bench::{self, Options};
+
+fn main() {
+    let mut args = pico_args::Arguments::from_env();
+    // `cargo bench` passes `--bench` to every bench target.
+    args.contains("--bench");
+
+    let options = Options {
+        save_baseline: args
+            .opt_value_from_str("--save-baseline")
+            .unwrap_or_else(exit),
+        baseline: args.opt_value_from_str("--baseline").unwrap_or_else(exit),
+        filter: args.opt_free_from_str().unwrap_or_else(exit),
+    };
+
+    bench::handle(&options);
+}
+
+fn exit<T>(err: pico_args::Error) -> T {
+    eprintln!("Error: {err}");
+    std::process::exit(1);
+}
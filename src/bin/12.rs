@@ -2,7 +2,7 @@ use std::fmt::Debug;
 use std::cmp;
 use std::collections::HashMap;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, variants: [(1, "naive", part_one_naive)]);
 
 #[derive(PartialEq, Eq, Clone)]
 enum Spring {
@@ -52,51 +52,52 @@ fn parse(input: &str) -> Vec<Record> {
          .collect()
 }
 
-// fn arrangements(springs: &[Spring], damaged: &[usize], i: usize, j: usize) -> usize {
-//     if i >= springs.len() {
-//         return if j >= damaged.len() { 1 } else { 0 }
-//     }
-//
-//     if j >= damaged.len() {
-//         return if springs[i..].contains(&Spring::Damaged) { 0 } else { 1 }
-//     }
-//
-//     match springs[i] {
-//         Spring::Operational => arrangements(springs, damaged, i+1, j),
-//         Spring::Unknown => {
-//             let mut new_springs: Vec<Spring> = springs.iter().map(|s| s.clone()).collect();
-//             new_springs[i] = Spring::Operational;
-//
-//             let arr1 = arrangements(&new_springs, damaged, i, j);
-//
-//             new_springs = springs.iter().map(|s| s.clone()).collect();
-//             new_springs[i] = Spring::Damaged;
-//
-//             let arr2 = arrangements(&new_springs, damaged, i, j);
-//             arr1 + arr2
-//         },
-//         Spring::Damaged => {
-//             let springs_len = springs.len() - i;
-//             let group_size = damaged[j];
-//
-//             if group_size > springs_len {
-//                 return 0;
-//             }
-//
-//             let group = &springs[i..i+group_size];
-//
-//             if group.contains(&Spring::Operational) {
-//                 return 0;
-//             }
-//
-//             if group_size < springs_len && springs[i+group_size] == Spring::Damaged {
-//                 return 0;
-//             }
-//
-//             arrangements(springs, damaged, i+group_size+1, j+1)
-//         }
-//     }
-// }
+// Brute force approach, kept around to cross-check the memoized version.
+fn arrangements_naive(springs: &[Spring], damaged: &[usize], i: usize, j: usize) -> usize {
+    if i >= springs.len() {
+        return if j >= damaged.len() { 1 } else { 0 }
+    }
+
+    if j >= damaged.len() {
+        return if springs[i..].contains(&Spring::Damaged) { 0 } else { 1 }
+    }
+
+    match springs[i] {
+        Spring::Operational => arrangements_naive(springs, damaged, i+1, j),
+        Spring::Unknown => {
+            let mut new_springs: Vec<Spring> = springs.to_vec();
+            new_springs[i] = Spring::Operational;
+
+            let arr1 = arrangements_naive(&new_springs, damaged, i, j);
+
+            new_springs = springs.to_vec();
+            new_springs[i] = Spring::Damaged;
+
+            let arr2 = arrangements_naive(&new_springs, damaged, i, j);
+            arr1 + arr2
+        },
+        Spring::Damaged => {
+            let springs_len = springs.len() - i;
+            let group_size = damaged[j];
+
+            if group_size > springs_len {
+                return 0;
+            }
+
+            let group = &springs[i..i+group_size];
+
+            if group.contains(&Spring::Operational) {
+                return 0;
+            }
+
+            if group_size < springs_len && springs[i+group_size] == Spring::Damaged {
+                return 0;
+            }
+
+            arrangements_naive(springs, damaged, i+group_size+1, j+1)
+        }
+    }
+}
 
 fn to_hash(springs: &[Spring], damaged: &[usize]) -> (String, String) {
     let springs: String = springs.iter().map(|s| match s {
@@ -178,6 +179,16 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(result)
 }
 
+pub fn part_one_naive(input: &str) -> Option<u64> {
+    let data = parse(input);
+
+    let result = data.iter()
+                     .map(|r| arrangements_naive(&r.springs, &r.damaged, 0, 0) as u64)
+                     .sum::<u64>();
+
+    Some(result)
+}
+
 pub fn part_two(input: &str) -> Option<u64> {
     let mut data = parse(input);
 
@@ -208,6 +219,12 @@ mod tests {
         assert_eq!(result, Some(21));
     }
 
+    #[test]
+    fn test_part_one_naive() {
+        let result = part_one_naive(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(21));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
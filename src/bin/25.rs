@@ -4,7 +4,7 @@ use rustworkx_core::connectivity::stoer_wagner_min_cut;
 use rustworkx_core::petgraph::Undirected;
 use rustworkx_core::petgraph::csr::Csr;
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(25, 1);
 
 type Graph<'a> = Csr<&'a str, (), Undirected>;
 
@@ -48,10 +48,6 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some((size1 * size2) as u32)
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,10 +57,4 @@ mod tests {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(54));
     }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
 }
@@ -0,0 +1,26 @@
+/// Camel Cards: one hand of five cards and a bid per line.
+use std::collections::HashSet;
+
+use super::Rng;
+
+const CARDS: [char; 13] = [
+    'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut seen = HashSet::new();
+    let mut lines = Vec::with_capacity(size);
+
+    while lines.len() < size {
+        // bias towards few distinct cards so that every hand type shows up.
+        let pool: Vec<char> = (0..rng.range(1, 5)).map(|_| *rng.choose(&CARDS)).collect();
+        let hand: String = (0..5).map(|_| *rng.choose(&pool)).collect();
+
+        // hands are unique in the real input, otherwise the ranking would be ambiguous.
+        if seen.insert(hand.clone()) {
+            lines.push(format!("{hand} {}", rng.range(1, 1000)));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
@@ -0,0 +1,38 @@
+/// Hot Springs: a row of springs and the sizes of its contiguous groups of damaged springs.
+use super::Rng;
+
+fn damaged_groups(row: &[char]) -> Vec<usize> {
+    row.split(|c| *c == '.')
+        .map(<[char]>::len)
+        .filter(|len| *len > 0)
+        .collect()
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut lines = Vec::with_capacity(size);
+
+    while lines.len() < size {
+        let len = rng.range(1, 20);
+        let row: Vec<char> = (0..len)
+            .map(|_| if rng.chance(1, 2) { '#' } else { '.' })
+            .collect();
+
+        let groups = damaged_groups(&row);
+
+        // every record lists at least one group.
+        if groups.is_empty() {
+            continue;
+        }
+
+        // hide some of the springs, so that the generated row is one of the possible arrangements.
+        let masked: String = row
+            .iter()
+            .map(|c| if rng.chance(1, 2) { '?' } else { *c })
+            .collect();
+
+        let groups: Vec<String> = groups.iter().map(ToString::to_string).collect();
+        lines.push(format!("{masked} {}", groups.join(",")));
+    }
+
+    lines.join("\n") + "\n"
+}
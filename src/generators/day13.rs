@@ -0,0 +1,113 @@
+/// Point of Incidence: patterns of ash (`.`) and rocks (`#`), separated by empty lines.
+///
+/// Every pattern has exactly one perfect line of reflection, and exactly one other line that becomes a
+/// perfect reflection after fixing a single smudge.
+use super::Rng;
+
+type Grid = Vec<Vec<bool>>;
+
+fn transpose(grid: &Grid) -> Grid {
+    (0..grid[0].len())
+        .map(|x| grid.iter().map(|row| row[x]).collect())
+        .collect()
+}
+
+/// Number of differing cells when reflecting the rows of `grid` across the line above row `i`.
+fn row_differences(grid: &Grid, i: usize) -> usize {
+    (0..i.min(grid.len() - i))
+        .map(|j| {
+            grid[i + j]
+                .iter()
+                .zip(&grid[i - j - 1])
+                .filter(|(a, b)| a != b)
+                .count()
+        })
+        .sum()
+}
+
+/// Counts the lines of reflection (horizontal and vertical) that have exactly `differences` smudges.
+fn count_reflections(grid: &Grid, differences: usize) -> usize {
+    let transposed = transpose(grid);
+    let rows = (1..grid.len()).filter(|&i| row_differences(grid, i) == differences);
+    let cols = (1..transposed.len()).filter(|&i| row_differences(&transposed, i) == differences);
+    rows.count() + cols.count()
+}
+
+fn generate_pattern(rng: &mut Rng) -> Grid {
+    loop {
+        let height = rng.range(5, 17);
+        let width = rng.range(5, 17);
+
+        // perfect reflection between rows, which leaves at least one row outside of its reach.
+        let row_line = rng.range(1, height - 1);
+        let reach = row_line.min(height - row_line);
+        if 2 * reach >= height {
+            continue;
+        }
+
+        // every row is symmetric along `col_line`, so mirroring rows keeps that symmetry.
+        let col_line = rng.range(1, width - 1);
+        let mut grid: Grid = (0..height)
+            .map(|_| {
+                let mut row: Vec<bool> = (0..width).map(|_| rng.chance(1, 2)).collect();
+                for j in 0..col_line.min(width - col_line) {
+                    row[col_line + j] = row[col_line - j - 1];
+                }
+                row
+            })
+            .collect();
+
+        for j in 0..reach {
+            grid[row_line + j] = grid[row_line - j - 1].clone();
+        }
+
+        // add a smudge outside of the row reflection, but inside of the column reflection.
+        let smudge_row = loop {
+            let y = rng.range(0, height - 1);
+            if y + reach < row_line || y >= row_line + reach {
+                break y;
+            }
+        };
+        let col_reach = col_line.min(width - col_line);
+        let smudge_col = rng.range(col_line - col_reach, col_line + col_reach - 1);
+        grid[smudge_row][smudge_col] = !grid[smudge_row][smudge_col];
+
+        if count_reflections(&grid, 0) == 1 && count_reflections(&grid, 1) == 1 {
+            return if rng.chance(1, 2) {
+                transpose(&grid)
+            } else {
+                grid
+            };
+        }
+    }
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            generate_pattern(rng)
+                .iter()
+                .map(|row| row.iter().map(|&rock| if rock { '#' } else { '.' }).collect())
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+        + "\n"
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{count_reflections, generate_pattern};
+    use crate::generators::Rng;
+
+    #[test]
+    fn generates_patterns_with_single_reflections() {
+        let mut rng = Rng::new(13);
+        for _ in 0..50 {
+            let grid = generate_pattern(&mut rng);
+            assert_eq!(count_reflections(&grid, 0), 1);
+            assert_eq!(count_reflections(&grid, 1), 1);
+        }
+    }
+}
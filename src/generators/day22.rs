@@ -0,0 +1,46 @@
+/// Sand Slabs: bricks as pairs of coordinates `x,y,z~x,y,z`, that may not intersect each other.
+use std::collections::HashSet;
+
+use super::Rng;
+
+const MAX_LENGTH: usize = 4;
+const PLANE_SIZE: usize = 9;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut occupied: HashSet<(usize, usize, usize)> = HashSet::new();
+    let mut lines = Vec::with_capacity(size);
+
+    // leave enough room in the air so that the bricks can be placed without too many retries.
+    let max_z = 2 + size * 2;
+
+    while lines.len() < size {
+        let start = (
+            rng.range(0, PLANE_SIZE),
+            rng.range(0, PLANE_SIZE),
+            rng.range(1, max_z),
+        );
+        let length = rng.range(0, MAX_LENGTH - 1);
+
+        let end = match rng.range(0, 2) {
+            0 => ((start.0 + length).min(PLANE_SIZE), start.1, start.2),
+            1 => (start.0, (start.1 + length).min(PLANE_SIZE), start.2),
+            _ => (start.0, start.1, start.2 + length),
+        };
+
+        let cubes: Vec<(usize, usize, usize)> = (start.0..=end.0)
+            .flat_map(|x| (start.1..=end.1).flat_map(move |y| (start.2..=end.2).map(move |z| (x, y, z))))
+            .collect();
+
+        if cubes.iter().any(|c| occupied.contains(c)) {
+            continue;
+        }
+
+        occupied.extend(cubes);
+        lines.push(format!(
+            "{},{},{}~{},{},{}",
+            start.0, start.1, start.2, end.0, end.1, end.2
+        ));
+    }
+
+    lines.join("\n") + "\n"
+}
@@ -0,0 +1,118 @@
+/// Random input generators for stress and property testing.
+///
+/// Every generator is deterministic for a given seed, and produces an input in the same format as the
+/// puzzle input of its day. The `size` parameter roughly controls how many records are generated.
+use crate::template::PuzzleId;
+
+mod day07;
+mod day12;
+mod day13;
+mod day22;
+
+/// A function that generates a puzzle input from a random source and a size.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// Returns the input generator for a given puzzle, if there is one.
+#[must_use]
+pub fn get_generator(puzzle: PuzzleId) -> Option<Generator> {
+    match (puzzle.year.into_inner(), puzzle.day.into_inner()) {
+        (2023, 7) => Some(day07::generate),
+        (2023, 12) => Some(day12::generate),
+        (2023, 13) => Some(day13::generate),
+        (2023, 22) => Some(day22::generate),
+        _ => None,
+    }
+}
+
+/// Generates an input for a given puzzle, seed and size. Returns [`None`] if the puzzle has no generator.
+#[must_use]
+pub fn generate(puzzle: PuzzleId, seed: u64, size: usize) -> Option<String> {
+    let generator = get_generator(puzzle)?;
+    Some(generator(&mut Rng::new(seed), size))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A small, seedable pseudo-random number generator (`SplitMix64`).
+///
+/// Not suitable for cryptography, but good enough to produce reproducible test inputs without
+/// depending on an external crate.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in the inclusive range `low..=high`.
+    pub fn range(&mut self, low: usize, high: usize) -> usize {
+        assert!(low <= high, "empty range {low}..={high}");
+        let span = (high - low) as u64 + 1;
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = (self.next_u64() % span) as usize;
+        low + offset
+    }
+
+    /// Returns `true` with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.next_u64() % denominator < numerator
+    }
+
+    /// Returns a random element of a non-empty slice.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() - 1)]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{generate, Rng};
+    use crate::template::PuzzleId;
+    use crate::{day, year};
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn rng_range_is_inclusive() {
+        let mut rng = Rng::new(1);
+        let values: Vec<usize> = (0..1000).map(|_| rng.range(3, 5)).collect();
+        assert!(values.iter().all(|v| (3..=5).contains(v)));
+        assert!(values.contains(&3));
+        assert!(values.contains(&5));
+    }
+
+    #[test]
+    fn generates_same_input_for_same_seed() {
+        for d in [day!(7), day!(12), day!(13), day!(22)] {
+            let puzzle = PuzzleId::new(year!(2023), d);
+            assert_eq!(generate(puzzle, 7, 20), generate(puzzle, 7, 20));
+            assert_ne!(generate(puzzle, 7, 20), generate(puzzle, 8, 20));
+        }
+    }
+
+    #[test]
+    fn returns_none_for_days_without_generator() {
+        assert_eq!(generate(PuzzleId::new(year!(2023), day!(1)), 0, 10), None);
+        assert_eq!(generate(PuzzleId::new(year!(2022), day!(7)), 0, 10), None);
+    }
+}
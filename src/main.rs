@@ -1,77 +1,237 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, compare, countdown, diff, download, leaderboard, read, scaffold, scale, solve, stars,
+    stress, time,
+};
+use advent_of_code::template::config;
 use args::{parse, AppArguments};
+use std::process;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::{Day, PuzzleId, Year};
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::{leaderboard, time};
+    use advent_of_code::template::config::Config;
+    use advent_of_code::template::runner::BenchConfig;
+    use advent_of_code::template::{Day, DaySelection, Limits, PuzzleId, SubmitOptions, Year};
+    use std::path::PathBuf;
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            selection: DaySelection,
+            force: bool,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+            part: Option<u8>,
+            refresh: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
+            force: bool,
         },
         Solve {
-            day: Day,
+            selection: DaySelection,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            submit: Option<SubmitOptions>,
+            threads: Option<usize>,
         },
         All {
+            selection: DaySelection,
+            release: bool,
+            limits: Limits,
+            jobs: usize,
+            threads: Option<usize>,
+        },
+        Compare {
+            rev_a: String,
+            rev_b: String,
+            selection: DaySelection,
+            rounds: usize,
+        },
+        Scale {
+            puzzle: PuzzleId,
+            max_threads: Option<usize>,
+            limits: Limits,
+        },
+        Stress {
+            puzzle: PuzzleId,
+            seed: u64,
+            size: usize,
+            runs: u64,
+            timeout: Duration,
             release: bool,
         },
         Time {
-            all: bool,
+            selection: Option<DaySelection>,
+            year: Year,
+            limits: Limits,
+            options: time::Options,
+        },
+        Stars {
+            year: Year,
+            calendar: Option<PathBuf>,
+        },
+        Diff {
+            puzzle: PuzzleId,
+            generated: u64,
+            seed: u64,
+            size: usize,
+            release: bool,
+        },
+        Countdown,
+        Leaderboard {
+            year: Year,
+            source: Option<leaderboard::Source>,
             day: Option<Day>,
-            store: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    pub fn parse(config: &Config) -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+        let subcommand = args.subcommand()?;
+        // every command except `today` can target another year than the primary year of the workspace.
+        let year: Year = args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::primary);
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let release = args.contains("--release");
+                let limits = parse_limits(&mut args, config)?;
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                let threads = args.opt_value_from_str("--threads")?;
+
+                AppArguments::All {
+                    selection: parse_selection(&mut args, year)?.unwrap_or_else(|| {
+                        let mut selection = DaySelection::all();
+                        selection.year = year;
+                        selection
+                    }),
+                    release,
+                    limits,
+                    jobs,
+                    threads,
+                }
+            }
             Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+                let options = time::Options {
+                    run_all: args.contains("--all"),
+                    store: args.contains("--store"),
+                    compare_variants: args.contains("--variants"),
+                    track_allocations: args.contains("--alloc"),
+                    table: args
+                        .opt_value_from_str("--table")?
+                        .or_else(|| config.readme_table.clone()),
+                    report: args.opt_value_from_str("--report")?,
+                    mask_answers: args.contains("--mask-answers"),
+                    threads: args.opt_value_from_str("--threads")?,
+                    bench: parse_bench_config(&mut args, config)?,
+                };
+                let limits = parse_limits(&mut args, config)?;
 
                 AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
+                    selection: parse_selection(&mut args, year)?,
+                    year,
+                    limits,
+                    options,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            Some("stress") => AppArguments::Stress {
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(0),
+                size: args.opt_value_from_str("--size")?.unwrap_or(100),
+                runs: args.opt_value_from_str("--runs")?.unwrap_or(100),
+                timeout: Duration::from_secs(args.opt_value_from_str("--timeout")?.unwrap_or(10)),
+                release: args.contains("--release"),
+            },
+            Some("compare") => {
+                let rounds = args.opt_value_from_str("--rounds")?.unwrap_or(5);
+                let rev_a = args.free_from_str()?;
+                let rev_b = args.free_from_str()?;
+                let mut selection = args.opt_free_from_str()?.unwrap_or_else(DaySelection::all);
+                selection.year = year;
+
+                AppArguments::Compare {
+                    rev_a,
+                    rev_b,
+                    selection,
+                    rounds,
+                }
+            }
+            Some("scale") => AppArguments::Scale {
+                max_threads: args.opt_value_from_str("--max-threads")?,
+                limits: parse_limits(&mut args, config)?,
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
+            },
+            Some("diff") => AppArguments::Diff {
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
+                generated: args.opt_value_from_str("--generated")?.unwrap_or(20),
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(0),
+                size: args.opt_value_from_str("--size")?.unwrap_or(100),
+                release: args.contains("--release"),
+            },
+            Some("stars") => AppArguments::Stars {
+                year,
+                calendar: args.opt_value_from_str("--calendar")?,
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            Some("download") => AppArguments::Download {
+                force: args.contains("--force"),
+                selection: parse_selection(&mut args, year)?.ok_or("missing day selection")?,
             },
+            Some("read") => {
+                let part = args.opt_value_from_str("--part")?;
+                if part.is_some_and(|part| part != 1 && part != 2) {
+                    eprintln!("`--part` must be 1 or 2.");
+                    process::exit(1);
+                }
+
+                AppArguments::Read {
+                    part,
+                    refresh: args.contains("--refresh"),
+                    puzzle: PuzzleId::new(year, args.free_from_str()?),
+                }
+            }
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
                 download: args.contains("--download"),
+                force: args.contains("--force"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = parse_submit_options(&mut args)?;
+                let dhat = args.contains("--dhat");
+                let threads = args.opt_value_from_str("--threads")?;
+
+                AppArguments::Solve {
+                    selection: parse_selection(&mut args, year)?.ok_or("missing day selection")?,
+                    release,
+                    submit,
+                    dhat,
+                    threads,
+                }
+            }
+            Some("countdown") => AppArguments::Countdown,
+            Some("leaderboard") => {
+                let file: Option<String> = args.opt_value_from_str("--file")?;
+                let day = args.opt_value_from_str("--day")?;
+                let id: Option<u64> = args.opt_free_from_str()?;
+
+                AppArguments::Leaderboard {
+                    year,
+                    source: file
+                        .map(leaderboard::Source::File)
+                        .or(id.map(leaderboard::Source::Id)),
+                    day,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -91,43 +251,200 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parse the `--submit <part>`, `--dry-run` and `--auto-submit` flags of `solve`, if any of them is set.
+    fn parse_submit_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<SubmitOptions>, pico_args::Error> {
+        let options = SubmitOptions {
+            part: args.opt_value_from_str("--submit")?,
+            dry_run: args.contains("--dry-run"),
+            auto: args.contains("--auto-submit"),
+        };
+
+        if options.part.is_some() || options.dry_run || options.auto {
+            Ok(Some(options))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parse a day selection like `1-5,9` along with the `--part <n>`, `--slow-only` and `--unsolved` filters.
+    /// Returns `None` if neither days nor filters were given.
+    fn parse_selection(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Option<DaySelection>, pico_args::Error> {
+        let part: Option<u8> = args.opt_value_from_str("--part")?;
+        let slow_only = args.contains("--slow-only");
+        let unsolved = args.contains("--unsolved");
+        let days: Option<DaySelection> = args.opt_free_from_str()?;
+
+        if days.is_none() && part.is_none() && !slow_only && !unsolved {
+            return Ok(None);
+        }
+
+        let mut selection = days.unwrap_or_else(DaySelection::all);
+        selection.year = year;
+        selection.part = part;
+        selection.slow_only = slow_only;
+        selection.unsolved = unsolved;
+        Ok(Some(selection))
+    }
+
+    /// Parse `--timeout <seconds>`, `--day-timeout <day>=<seconds>` and `--memory-limit <megabytes>`, falling back
+    /// to the limits of the config.
+    fn parse_limits(
+        args: &mut pico_args::Arguments,
+        config: &Config,
+    ) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            timeout: args
+                .opt_value_from_str("--timeout")?
+                .map(Duration::from_secs)
+                .or(config.timeout),
+            day_timeouts: args
+                .values_from_fn("--day-timeout", parse_day_timeout)?
+                .into_iter()
+                .collect(),
+            memory_limit_mb: args
+                .opt_value_from_str("--memory-limit")?
+                .or(config.memory_limit_mb),
+        })
+    }
+
+    /// Parse `--warm-up <ms>`, `--bench-time <ms>` and `--precision <percent>`, falling back to the config.
+    fn parse_bench_config(
+        args: &mut pico_args::Arguments,
+        config: &Config,
+    ) -> Result<BenchConfig, pico_args::Error> {
+        let default = config.bench;
+        Ok(BenchConfig {
+            warm_up: args
+                .opt_value_from_str("--warm-up")?
+                .map_or(default.warm_up, Duration::from_millis),
+            budget: args
+                .opt_value_from_str("--bench-time")?
+                .map_or(default.budget, Duration::from_millis),
+            precision: args
+                .opt_value_from_str::<_, f64>("--precision")?
+                .map_or(default.precision, |percent| percent / 100.0),
+        })
+    }
+
+    fn parse_day_timeout(s: &str) -> Result<(Day, Duration), String> {
+        let (day, secs) = s.split_once('=').ok_or("expecting a value like `05=120`")?;
+        let day = day.parse::<Day>().map_err(|e| e.to_string())?;
+        let secs = secs.parse::<u64>().map_err(|e| e.to_string())?;
+        Ok((day, Duration::from_secs(secs)))
+    }
 }
 
 fn main() {
-    match parse() {
+    let config = match config::init() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    match parse(config) {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            AppArguments::All {
+                selection,
+                release,
+                limits,
+                jobs,
+                threads,
+            } => all::handle(&selection, release, &limits, jobs, threads),
+            AppArguments::Scale {
+                puzzle,
+                max_threads,
+                limits,
+            } => scale::handle(puzzle, max_threads, &limits),
+            AppArguments::Compare {
+                rev_a,
+                rev_b,
+                selection,
+                rounds,
+            } => compare::handle(&rev_a, &rev_b, &selection, rounds),
+            AppArguments::Time {
+                selection,
+                year,
+                limits,
+                options,
+            } => time::handle(config, selection, year, &limits, &options),
+            AppArguments::Diff {
+                puzzle,
+                generated,
+                seed,
+                size,
+                release,
+            } => diff::handle(puzzle, generated, seed, size, release),
+            AppArguments::Stress {
+                puzzle,
+                seed,
+                size,
+                runs,
+                timeout,
+                release,
+            } => stress::handle(puzzle, seed, size, runs, timeout, release),
+            AppArguments::Stars { year, calendar } => stars::handle(year, calendar.as_deref()),
+            AppArguments::Download { selection, force } => {
+                download::handle_selection(config, &selection, force);
+            }
+            AppArguments::Read {
+                puzzle,
+                part,
+                refresh,
+            } => read::handle(puzzle, part, refresh),
+            AppArguments::Scaffold {
+                puzzle,
+                download,
+                force,
+            } => {
+                scaffold::handle(config, puzzle);
                 if download {
-                    download::handle(day);
+                    if let Err(e) = download::handle(config, puzzle, force) {
+                        eprintln!("{e}");
+                        process::exit(1);
+                    }
                 }
             }
             AppArguments::Solve {
-                day,
+                selection,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                threads,
+            } => solve::handle(&selection, release, dhat, submit, threads),
+            AppArguments::Countdown => countdown::handle(config),
+            AppArguments::Leaderboard { year, source, day } => {
+                leaderboard::handle(config, year, source, day);
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = PuzzleId::primary(day);
+                        scaffold::handle(config, puzzle);
+                        if let Err(e) = download::handle(config, puzzle, false) {
+                            eprintln!("{e}");
+                            process::exit(1);
+                        }
+                        read::handle(puzzle, None, false)
                     }
                     None => {
+                        // mirrors the range that `Day::today` accepts.
                         eprintln!(
                             "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            the {}th of december. Please use `scaffold` with a specific day.",
+                            Year::current().day_count()
                         );
                         process::exit(1)
                     }
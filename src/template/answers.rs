@@ -0,0 +1,213 @@
+/// Module that records the answers that were accepted by advent of code.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Path of the answers file of a year, e.g. `data/answers.json` for the primary year.
+#[must_use]
+pub fn get_path(year: Year) -> PathBuf {
+    year.data_dir().join(ANSWERS_FILE_NAME)
+}
+
+/// An answer that advent of code accepted as correct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part: u8,
+    pub value: String,
+}
+
+/// Represents the accepted answers of a year.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let path = get_path(year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers of a year from a JSON file. If not present, returns no answers.
+    /// Files that can not be read result in an error, so that they are not overwritten.
+    pub fn read_from_file(year: Year) -> Result<Self, Error> {
+        let path = get_path(year);
+
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+
+        let s = fs::read_to_string(&path)?;
+        Answers::try_from(s).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Could not read answers from `{}`: {e}", path.display()),
+            )
+        })
+    }
+
+    /// Record an accepted answer, replacing a previous answer for the same part.
+    pub fn record(&mut self, day: Day, part: u8, value: &str) {
+        self.data.retain(|a| !(a.day == day && a.part == part));
+        self.data.push(Answer {
+            day,
+            part,
+            value: value.into(),
+        });
+        self.data.sort_unstable_by_key(|a| (a.day, a.part));
+    }
+
+    /// The accepted answer for a part, if there is one.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|a| a.day == day && a.part == part)
+            .map(|a| a.value.as_str())
+    }
+
+    pub fn is_solved(&self, day: Day, part: u8) -> bool {
+        self.get(day, part).is_some()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.value.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|p| **p == 1.0 || **p == 2.0)
+            .map(|p| *p as u8)
+            .ok_or("Expected answer.part to be 1 or 2.")?;
+
+        let value = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.answer to be a string.")?;
+
+        Ok(Answer {
+            day,
+            part,
+            value: value.clone(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn records_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(2), 1, "12");
+        answers.record(day!(1), 2, "7");
+        answers.record(day!(2), 1, "13");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(2), 1), Some("13"));
+        assert!(!answers.is_solved(day!(2), 2));
+    }
+
+    #[test]
+    fn serializes_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "42");
+        answers.record(day!(1), 2, "abc");
+
+        let json = JsonValue::from(answers).stringify().unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.get(day!(1), 1), Some("42"));
+        assert_eq!(parsed.get(day!(1), 2), Some("abc"));
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        let json = r#"{"data":[{"day":"01","part":3,"answer":"1"}]}"#.to_string();
+        assert!(Answers::try_from(json).is_err());
+    }
+}
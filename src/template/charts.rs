@@ -0,0 +1,328 @@
+/// Module that renders benchmark timings as a self-contained SVG image that can be embedded in the readme.
+/// The image holds a log-scale bar chart of the run time per day and part and a chart of the cumulative run time.
+use std::fmt::Write;
+use std::{fs, io::Error, path::PathBuf};
+
+use crate::template::readme_benchmarks::format_nanos;
+use crate::template::timings::Timings;
+use crate::template::Year;
+
+const ASSETS_DIR: &str = ".assets";
+
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const CHART_HEIGHT: f64 = 200.0;
+const BAR_WIDTH: f64 = 8.0;
+const GROUP_WIDTH: f64 = 24.0;
+
+const COLOR_PART_1: &str = "#9999cc";
+const COLOR_PART_2: &str = "#ffff66";
+const COLOR_TEXT: &str = "#808080";
+const COLOR_GRID: &str = "#80808040";
+
+/// Path of the chart image, e.g. `.assets/benchmarks.svg` for the default table of the primary year.
+#[must_use]
+pub fn get_path(year: Year, table: Option<&str>) -> PathBuf {
+    let mut name = String::from("benchmarks");
+    if !year.is_primary() {
+        write!(name, "-{year}").unwrap();
+    }
+    if let Some(table) = table {
+        write!(name, ".{table}").unwrap();
+    }
+    PathBuf::from(ASSETS_DIR).join(format!("{name}.svg"))
+}
+
+/// Maps run times onto the vertical axis of a chart, spanning whole decades of nanoseconds.
+struct LogScale {
+    min_exp: i32,
+    max_exp: i32,
+}
+
+impl LogScale {
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(values: impl Iterator<Item = f64>) -> Self {
+        let (min, max) = values
+            .filter(|v| *v > 0.0)
+            .fold((f64::MAX, f64::MIN), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+
+        if min > max {
+            return LogScale {
+                min_exp: 3,
+                max_exp: 6,
+            };
+        }
+
+        let min_exp = min.log10().floor() as i32;
+        let max_exp = (max.log10().ceil() as i32).max(min_exp + 1);
+        LogScale { min_exp, max_exp }
+    }
+
+    /// Distance from the bottom of the chart, between 0 and `CHART_HEIGHT`.
+    fn height(&self, nanos: f64) -> f64 {
+        let range = f64::from(self.max_exp - self.min_exp);
+        let value = nanos.max(1.0).log10() - f64::from(self.min_exp);
+        (value / range).clamp(0.0, 1.0) * CHART_HEIGHT
+    }
+}
+
+/// Render timings of a year as SVG. Days are laid out in the order of the timings.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn render(timings: &Timings, year: Year) -> String {
+    let days = timings.data.len().max(1) as f64;
+    let width = MARGIN_LEFT + days * GROUP_WIDTH + MARGIN_RIGHT;
+
+    let bars_top = 40.0;
+    let bars_bottom = bars_top + CHART_HEIGHT;
+    let total_top = bars_bottom + 72.0;
+    let total_bottom = total_top + CHART_HEIGHT;
+    let height = total_bottom + 40.0;
+
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="10">"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{MARGIN_LEFT}" y="20" font-size="14" fill="{COLOR_TEXT}">{year} run time per part (log scale)</text>"#
+    )
+    .unwrap();
+
+    render_bars(&mut svg, timings, bars_bottom, width);
+    render_day_labels(&mut svg, timings, bars_bottom + 14.0);
+
+    writeln!(
+        svg,
+        r#"<text x="{MARGIN_LEFT}" y="{}" font-size="14" fill="{COLOR_TEXT}">{year} cumulative run time</text>"#,
+        total_top - 20.0
+    )
+    .unwrap();
+
+    render_cumulative(&mut svg, timings, total_bottom, width);
+    render_day_labels(&mut svg, timings, total_bottom + 14.0);
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn group_x(index: usize) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let index = index as f64;
+    MARGIN_LEFT + index * GROUP_WIDTH
+}
+
+fn render_bars(svg: &mut String, timings: &Timings, bottom: f64, width: f64) {
+    let scale = LogScale::new(
+        timings
+            .data
+            .iter()
+            .flat_map(|t| [t.part_nanos(1), t.part_nanos(2)])
+            .flatten(),
+    );
+
+    for exp in scale.min_exp..=scale.max_exp {
+        let y = bottom - scale.height(10_f64.powi(exp));
+        writeln!(
+            svg,
+            r#"<line x1="{MARGIN_LEFT}" y1="{y}" x2="{}" y2="{y}" stroke="{COLOR_GRID}"/>"#,
+            width - MARGIN_RIGHT
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="end" fill="{COLOR_TEXT}">{}</text>"#,
+            MARGIN_LEFT - 6.0,
+            y + 3.0,
+            format_decade(exp)
+        )
+        .unwrap();
+    }
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        for (part, color) in [(1, COLOR_PART_1), (2, COLOR_PART_2)] {
+            let Some(nanos) = timing.part_nanos(part) else {
+                continue;
+            };
+
+            let bar_height = scale.height(nanos);
+            let x = group_x(i) + 4.0 + f64::from(part - 1) * BAR_WIDTH;
+            writeln!(
+                svg,
+                r#"<rect class="bar" x="{x}" y="{}" width="{BAR_WIDTH}" height="{bar_height}" fill="{color}"><title>Day {} part {part}: {}</title></rect>"#,
+                bottom - bar_height,
+                timing.day,
+                format_nanos(nanos)
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn render_cumulative(svg: &mut String, timings: &Timings, bottom: f64, width: f64) {
+    let total_nanos = timings.total_millis() * 1_000_000_f64;
+
+    writeln!(
+        svg,
+        r#"<line x1="{MARGIN_LEFT}" y1="{bottom}" x2="{}" y2="{bottom}" stroke="{COLOR_GRID}"/>"#,
+        width - MARGIN_RIGHT
+    )
+    .unwrap();
+
+    if total_nanos <= 0.0 {
+        return;
+    }
+
+    let top = bottom - CHART_HEIGHT;
+    writeln!(
+        svg,
+        r#"<line x1="{MARGIN_LEFT}" y1="{top}" x2="{}" y2="{top}" stroke="{COLOR_GRID}"/>"#,
+        width - MARGIN_RIGHT
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="end" fill="{COLOR_TEXT}">{}</text>"#,
+        MARGIN_LEFT - 6.0,
+        top + 3.0,
+        format_nanos(total_nanos)
+    )
+    .unwrap();
+
+    let mut sum = 0.0;
+    let mut points = vec![format!("{MARGIN_LEFT},{bottom}")];
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        sum += timing.total_nanos;
+        let x = group_x(i) + GROUP_WIDTH / 2.0;
+        let y = bottom - sum / total_nanos * CHART_HEIGHT;
+        points.push(format!("{x},{y}"));
+        writeln!(
+            svg,
+            r#"<circle cx="{x}" cy="{y}" r="2.5" fill="{COLOR_PART_2}"><title>Day {}: {} total</title></circle>"#,
+            timing.day,
+            format_nanos(sum)
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        svg,
+        r#"<polyline points="{}" fill="none" stroke="{COLOR_PART_1}" stroke-width="2"/>"#,
+        points.join(" ")
+    )
+    .unwrap();
+}
+
+fn render_day_labels(svg: &mut String, timings: &Timings, y: f64) {
+    for (i, timing) in timings.data.iter().enumerate() {
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{y}" text-anchor="middle" fill="{COLOR_TEXT}">{}</text>"#,
+            group_x(i) + GROUP_WIDTH / 2.0,
+            timing.day
+        )
+        .unwrap();
+    }
+}
+
+fn format_decade(exp: i32) -> String {
+    let units = ["ns", "µs", "ms", "s"];
+    let unit = usize::try_from(exp.div_euclid(3)).map_or(0, |u| u.min(units.len() - 1));
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+    let value = 10_u64.pow((exp - unit as i32 * 3).max(0) as u32);
+    format!("{value}{}", units[unit])
+}
+
+/// Render timings of a year and write the chart image for a benchmark table.
+pub fn update(timings: &Timings, year: Year, table: Option<&str>) -> Result<PathBuf, Error> {
+    let path = get_path(year, table);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, render(timings, year))?;
+    Ok(path)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{format_decade, get_path, render, LogScale, CHART_HEIGHT};
+    use crate::day;
+    use crate::template::timings::{Timing, TimingStats, Timings};
+    use crate::template::Year;
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1_nanos: Some(10e3),
+                    part_2_nanos: Some(20e6),
+                    total_nanos: 20_010_000_f64,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1_nanos: Some(1e9),
+                    part_2_nanos: None,
+                    total_nanos: 1_000_000_000_f64,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_bar_per_part() {
+        let svg = render(&get_mock_timings(), Year::primary());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("class=\"bar\"").count(), 3);
+        assert!(svg.contains("<title>Day 01 part 2: 20.0ms</title>"));
+        assert!(svg.contains("<title>Day 02: 1.0s total</title>"));
+    }
+
+    #[test]
+    fn renders_empty_timings() {
+        let svg = render(&Timings::default(), Year::primary());
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("class=\"bar\"").count(), 0);
+        assert!(!svg.contains("<polyline"));
+    }
+
+    #[test]
+    fn scales_logarithmically() {
+        let scale = LogScale::new([10_000_f64, 1_000_000_000_f64].into_iter());
+        assert_eq!((scale.min_exp, scale.max_exp), (4, 9));
+        assert!(scale.height(10_000_f64).abs() < f64::EPSILON);
+        assert!((scale.height(1_000_000_000_f64) - CHART_HEIGHT).abs() < f64::EPSILON);
+        assert!((scale.height(10_000_000_f64) - CHART_HEIGHT * 0.6).abs() < 1e-9);
+        assert_eq!(format_decade(4), "10µs");
+        assert_eq!(format_decade(9), "1s");
+        assert_eq!(format_decade(11), "100s");
+    }
+
+    #[test]
+    fn names_chart_after_table() {
+        let year = Year::new(2022).unwrap();
+        assert_eq!(
+            get_path(Year::primary(), None),
+            PathBuf::from(".assets/benchmarks.svg")
+        );
+        assert_eq!(
+            get_path(year, Some("laptop")),
+            PathBuf::from(".assets/benchmarks-2022.laptop.svg")
+        );
+    }
+}
@@ -0,0 +1,86 @@
+/// Module with the clock that waits for puzzles to unlock, which tests replace with a simulated one.
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The clock of the operating system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Wait until `instant`, calling `tick` with the remaining time before every sleep of at most a second.
+pub fn wait_until(clock: &impl Clock, instant: SystemTime, mut tick: impl FnMut(Duration)) {
+    loop {
+        let Ok(remaining) = instant.duration_since(clock.now()) else {
+            return;
+        };
+        if remaining.is_zero() {
+            return;
+        }
+
+        tick(remaining);
+
+        // sleep until the next full second of the countdown, so that it does not skip any.
+        let fraction = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        let step = if fraction.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            fraction
+        };
+        clock.sleep(step.min(remaining));
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::cell::Cell;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::{wait_until, Clock};
+
+    struct FakeClock(Cell<SystemTime>);
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.0.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.0.set(self.0.get() + duration);
+        }
+    }
+
+    #[test]
+    fn waits_until_instant() {
+        let clock = FakeClock(Cell::new(UNIX_EPOCH + Duration::from_millis(500)));
+        let target = UNIX_EPOCH + Duration::from_secs(3);
+        let mut ticks = vec![];
+
+        wait_until(&clock, target, |remaining| {
+            ticks.push(remaining.as_millis())
+        });
+
+        assert_eq!(clock.now(), target);
+        assert_eq!(ticks, vec![2500, 2000, 1000]);
+    }
+
+    #[test]
+    fn returns_for_past_instants() {
+        let clock = FakeClock(Cell::new(UNIX_EPOCH + Duration::from_secs(10)));
+        let mut ticks = 0;
+        wait_until(&clock, UNIX_EPOCH, |_| ticks += 1);
+        assert_eq!(ticks, 0);
+    }
+}
@@ -1,5 +1,29 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::{DaySelection, Limits};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(
+    selection: &DaySelection,
+    is_release: bool,
+    limits: &Limits,
+    jobs: usize,
+    threads: Option<usize>,
+) {
+    let options = RunOptions {
+        is_release,
+        jobs,
+        part: selection.part,
+        threads,
+        ..RunOptions::default()
+    };
+
+    let days = match selection.resolve() {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    run_multi(&days, selection.year, limits, &options);
 }
@@ -0,0 +1,197 @@
+use std::path::Path;
+use std::{fs, process};
+
+use crate::template::criterion::{self, Estimates, Measurement, Verdict};
+use crate::template::readme_benchmarks::format_nanos;
+use crate::template::run_multi::child_commands;
+use crate::template::PuzzleId;
+
+/// Flags of the `cargo bench` harness, named like criterion's.
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Only bench parts whose id, e.g. `2023 day 05/part 2`, contains this string.
+    pub filter: Option<String>,
+    /// Save the results to this baseline instead of `base`.
+    pub save_baseline: Option<String>,
+    /// Compare against this baseline without overwriting it.
+    pub baseline: Option<String>,
+}
+
+impl Options {
+    fn matches(&self, full_id: &str) -> bool {
+        self.filter
+            .as_ref()
+            .is_none_or(|filter| full_id.contains(filter.as_str()))
+    }
+}
+
+/// Bench every part of every solution in `src/bin` that has an input, and store the samples like criterion.
+/// Each solution runs in a single process, so its input is only read once.
+pub fn handle(options: &Options) {
+    if options.save_baseline.is_some() && options.baseline.is_some() {
+        eprintln!("`--save-baseline` can not be combined with `--baseline`.");
+        process::exit(1);
+    }
+
+    let puzzles: Vec<PuzzleId> = discover_puzzles()
+        .into_iter()
+        .filter(|puzzle| puzzle.input_path().exists())
+        .filter(|puzzle| {
+            (1..=puzzle.part_count()).any(|part| options.matches(&format!("{puzzle}/part {part}")))
+        })
+        .collect();
+
+    if puzzles.is_empty() {
+        eprintln!("There are no solutions with inputs to bench.");
+        process::exit(1);
+    }
+
+    if let Err(e) = child_commands::build_solutions(&puzzles, true) {
+        eprintln!("Failed to build solutions: {e}");
+        process::exit(1);
+    }
+
+    let home = criterion::home();
+    let mut failures = 0;
+
+    for puzzle in puzzles {
+        let lines = match child_commands::run_bin(puzzle, true, &["--time", "--export-samples"]) {
+            Ok(lines) => lines,
+            Err(e) => {
+                eprintln!("Failed to bench {puzzle}: {e}");
+                failures += 1;
+                continue;
+            }
+        };
+
+        for measurement in parse_samples(puzzle, &lines) {
+            if !options.matches(&measurement.full_id()) {
+                continue;
+            }
+
+            if let Err(e) = report(&home, &measurement, options) {
+                eprintln!("{e}");
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+/// Solutions in `src/bin`, in the order of their years and days.
+fn discover_puzzles() -> Vec<PuzzleId> {
+    let mut puzzles: Vec<PuzzleId> = fs::read_dir("src/bin")
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+                    PuzzleId::from_bin_name(path.file_stem()?.to_str()?)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    puzzles.sort_unstable();
+    puzzles
+}
+
+/// Parse the `Samples part <n>: …` lines that solutions print with `--export-samples`.
+fn parse_samples(puzzle: PuzzleId, lines: &[String]) -> Vec<Measurement> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let (part, samples) = line.strip_prefix("Samples part ")?.split_once(": ")?;
+            let times: Vec<f64> = samples
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .ok()?;
+
+            Some(Measurement {
+                group_id: puzzle.to_string(),
+                function_id: format!("part {part}"),
+                times,
+            })
+            .filter(|m| !m.times.is_empty())
+        })
+        .collect()
+}
+
+/// Save a measurement, compare it to its baseline and print the results like criterion.
+fn report(home: &Path, measurement: &Measurement, options: &Options) -> Result<(), String> {
+    let estimates = Estimates::from_measurement(measurement);
+
+    let (compare_to, save_to) = match (&options.baseline, &options.save_baseline) {
+        (Some(baseline), _) => (baseline.as_str(), None),
+        (None, Some(baseline)) => (baseline.as_str(), Some(baseline.as_str())),
+        (None, None) => (
+            criterion::DEFAULT_BASELINE,
+            Some(criterion::DEFAULT_BASELINE),
+        ),
+    };
+
+    let base = match criterion::read_estimates(home, measurement, compare_to) {
+        Some(base) => Some(base?),
+        None if options.baseline.is_some() => {
+            return Err(format!(
+                "Baseline `{compare_to}` does not exist for {}.",
+                measurement.full_id()
+            ))
+        }
+        None => None,
+    };
+
+    let save = |baseline: &str| {
+        criterion::save(home, measurement, &estimates, baseline)
+            .map_err(|e| format!("Could not save {}: {e}", measurement.full_id()))
+    };
+    save(criterion::NEW_BASELINE)?;
+    if let Some(baseline) = save_to {
+        save(baseline)?;
+    }
+
+    let mean = estimates.mean;
+    println!(
+        "{:<24}time:   [{} {} {}]",
+        measurement.full_id(),
+        format_nanos(mean.lower_bound),
+        format_nanos(mean.point_estimate),
+        format_nanos(mean.upper_bound)
+    );
+
+    if let Some(base) = base {
+        let change = criterion::compare(&base, &estimates);
+        println!(
+            "{:<24}change: [{:+.2}% {:+.2}% {:+.2}%]",
+            "",
+            change.lower_bound * 100.0,
+            change.point_estimate * 100.0,
+            change.upper_bound * 100.0
+        );
+        let verdict = match change.verdict {
+            Verdict::Improved => "Performance has improved.",
+            Verdict::Regressed => "Performance has regressed.",
+            Verdict::NoChange => "No change in performance detected.",
+        };
+        println!("{:<24}{verdict}", "");
+    }
+
+    let outliers = measurement.outliers();
+    if outliers > 0 {
+        #[allow(clippy::cast_precision_loss)]
+        let share = outliers as f64 / measurement.times.len() as f64 * 100.0;
+        println!(
+            "Found {outliers} outliers among {} measurements ({share:.2}%)",
+            measurement.times.len()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::env;
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+
+use crate::template::readme_benchmarks::format_nanos;
+use crate::template::run_multi::child_commands;
+use crate::template::stats::{self, welch_t_test};
+use crate::template::{all_days, DaySelection, PuzzleId};
+
+/// A revision that is checked out into a temporary git worktree. The worktree is removed when dropped.
+struct Worktree {
+    rev: String,
+    path: PathBuf,
+    target_dir: PathBuf,
+}
+
+impl Worktree {
+    fn add(rev: &str, label: &str) -> Result<Self, String> {
+        let commit = git(&["rev-parse", "--verify", &format!("{rev}^{{commit}}")])
+            .map_err(|e| format!("Unknown revision `{rev}`: {e}"))?;
+
+        let path =
+            env::temp_dir().join(format!("advent_of_code_compare_{}_{label}", process::id()));
+        git(&[
+            "worktree",
+            "add",
+            "--detach",
+            &path.to_string_lossy(),
+            &commit,
+        ])?;
+
+        // keep the build artifacts of each side in the project, so that dependencies are only compiled once.
+        let target_dir = env::current_dir()
+            .map_err(|e| e.to_string())?
+            .join("target")
+            .join("compare")
+            .join(label);
+
+        Ok(Worktree {
+            rev: rev.into(),
+            path,
+            target_dir,
+        })
+    }
+
+    fn has_solution(&self, puzzle: PuzzleId) -> bool {
+        self.path
+            .join("src/bin")
+            .join(format!("{}.rs", puzzle.bin_name()))
+            .exists()
+    }
+
+    fn build(&self, puzzles: &[PuzzleId]) -> Result<(), String> {
+        let mut args = vec!["build".to_string(), "--release".to_string()];
+        for puzzle in puzzles {
+            args.push("--bin".into());
+            args.push(puzzle.bin_name());
+        }
+
+        let status = Command::new("cargo")
+            .args(&args)
+            .current_dir(&self.path)
+            .env("CARGO_TARGET_DIR", &self.target_dir)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .map_err(|e| e.to_string())?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("Failed to build `{}`.", self.rev))
+        }
+    }
+
+    /// Bench a puzzle with the binary of this revision. The input is read from the current directory,
+    /// so that both revisions solve the same input.
+    fn bench(&self, puzzle: PuzzleId) -> Result<[Option<f64>; 2], String> {
+        let bin = child_commands::get_target_path_in(&self.target_dir, puzzle, true);
+        let output = Command::new(bin)
+            .arg("--time")
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|e| e.to_string())?;
+
+        if !output.status.success() {
+            return Err(format!("{puzzle} failed for `{}`.", self.rev));
+        }
+
+        let lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(ToString::to_string)
+            .collect();
+        let timing = child_commands::parse_exec_time(&lines, puzzle.day);
+        Ok([timing.part_1_nanos, timing.part_2_nanos])
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if let Err(e) = git(&[
+            "worktree",
+            "remove",
+            "--force",
+            &self.path.to_string_lossy(),
+        ]) {
+            eprintln!("Failed to remove worktree {}: {e}", self.path.display());
+        }
+    }
+}
+
+fn git(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Bench the selected days at two revisions and print the speedup of each part.
+/// Each round benches every day once per revision, alternating which one goes first, so that drift of the
+/// machine, e.g. thermal throttling, affects both revisions alike.
+pub fn handle(rev_a: &str, rev_b: &str, selection: &DaySelection, rounds: usize) {
+    if rounds < 2 {
+        eprintln!("`--rounds` must be at least 2 to test the significance of changes.");
+        process::exit(1);
+    }
+
+    // `compare` returns its errors instead of exiting, since `process::exit` does not run the destructors that
+    // remove the worktrees.
+    if let Err(e) = compare(rev_a, rev_b, selection, rounds) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+fn compare(
+    rev_a: &str,
+    rev_b: &str,
+    selection: &DaySelection,
+    rounds: usize,
+) -> Result<(), String> {
+    // the selection is resolved before any worktree exists, so an error needs no cleanup.
+    let days = selection.resolve().map_err(|e| e.to_string())?;
+    let puzzles: Vec<PuzzleId> = all_days()
+        .filter(|day| days.contains(day))
+        .map(|day| PuzzleId::new(selection.year, day))
+        .filter(|puzzle| puzzle.input_path().exists())
+        .collect();
+
+    if puzzles.is_empty() {
+        return Err("There are no days with an input to compare.".into());
+    }
+
+    let worktrees = [Worktree::add(rev_a, "a")?, Worktree::add(rev_b, "b")?];
+
+    let puzzles: Vec<PuzzleId> = puzzles
+        .into_iter()
+        .filter(|puzzle| {
+            let found = worktrees.iter().all(|w| w.has_solution(*puzzle));
+            if !found {
+                println!("Skipping {puzzle}, it is not solved at both revisions.");
+            }
+            found
+        })
+        .collect();
+
+    if puzzles.is_empty() {
+        return Err("There are no days to compare.".into());
+    }
+
+    for worktree in &worktrees {
+        worktree.build(&puzzles)?;
+    }
+
+    // mean run time of each round, per puzzle, part and revision.
+    let mut samples: HashMap<(PuzzleId, u8), [Vec<f64>; 2]> = HashMap::new();
+
+    for round in 0..rounds {
+        println!("Round {}/{rounds}...", round + 1);
+
+        for puzzle in &puzzles {
+            let order = if round % 2 == 0 { [0, 1] } else { [1, 0] };
+
+            for side in order {
+                let parts = worktrees[side].bench(*puzzle)?;
+
+                for (part, nanos) in (1..).zip(parts) {
+                    if let Some(nanos) = nanos {
+                        samples.entry((*puzzle, part)).or_default()[side].push(nanos);
+                    }
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("{}", format_table(rev_a, rev_b, &puzzles, &samples));
+    println!();
+    println!(
+        "Speedup is the run time at `{rev_a}` divided by the run time at `{rev_b}`. \
+        Significance of Welch's t-test over {rounds} rounds: * p < 0.05, ** p < 0.01, *** p < 0.001."
+    );
+
+    Ok(())
+}
+
+fn format_table(
+    rev_a: &str,
+    rev_b: &str,
+    puzzles: &[PuzzleId],
+    samples: &HashMap<(PuzzleId, u8), [Vec<f64>; 2]>,
+) -> String {
+    let width_a = rev_a.len().max(10);
+    let width_b = rev_b.len().max(10);
+
+    let mut lines = vec![
+        format!("| Day | Part | {rev_a:>width_a$} | {rev_b:>width_b$} | Speedup | p-value     |"),
+        format!(
+            "| --- | ---- | {:->width_a$} | {:->width_b$} | ------- | ----------- |",
+            "", ""
+        ),
+    ];
+
+    for puzzle in puzzles {
+        for part in 1..=2 {
+            let Some([a, b]) = samples.get(&(*puzzle, part)) else {
+                continue;
+            };
+
+            if a.is_empty() || b.is_empty() {
+                continue;
+            }
+
+            let (mean_a, mean_b) = (stats::mean(a), stats::mean(b));
+            let speedup = if mean_b > 0.0 { mean_a / mean_b } else { 0.0 };
+            let p = welch_t_test(a, b).map_or_else(
+                || "-".into(),
+                |test| format!("{:.3} {}", test.p, significance(test.p)),
+            );
+
+            lines.push(format!(
+                "| {:>3} | {part:>4} | {:>width_a$} | {:>width_b$} | {:>6.2}× | {p:<11} |",
+                puzzle.day.to_string(),
+                format_nanos(mean_a),
+                format_nanos(mean_b),
+                speedup,
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn significance(p: f64) -> &'static str {
+    if p < 0.001 {
+        "***"
+    } else if p < 0.01 {
+        "**"
+    } else if p < 0.05 {
+        "*"
+    } else {
+        ""
+    }
+}
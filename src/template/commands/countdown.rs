@@ -0,0 +1,33 @@
+use std::io::{stdout, Write};
+use std::path::Path;
+use std::process;
+
+use crate::template::clock::{self, Clock, SystemClock};
+use crate::template::commands::{download, read, scaffold};
+use crate::template::config::Config;
+use crate::template::http::format_remaining;
+use crate::template::PuzzleId;
+
+/// Count down to the next puzzle and scaffold, download and read it once it unlocks.
+pub fn handle(config: &Config) {
+    let clock = SystemClock;
+    let puzzle = PuzzleId::next_unlock(clock.now());
+
+    clock::wait_until(&clock, puzzle.unlock_time(), |remaining| {
+        print!("\r🎄 {puzzle} unlocks in {}  ", format_remaining(remaining));
+        let _ = stdout().flush();
+    });
+
+    println!("\r🎄 {puzzle} is unlocked!                ");
+
+    if Path::new(&puzzle.bin_path()).exists() {
+        println!("Module file \"{}\" already exists.", puzzle.bin_path());
+    } else {
+        scaffold::handle(config, puzzle);
+    }
+    if let Err(e) = download::handle(config, puzzle, false) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+    read::handle(puzzle, None, false);
+}
@@ -0,0 +1,132 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::generators;
+use crate::template::run_multi::child_commands;
+use crate::template::{variants, PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(puzzle: PuzzleId, generated: u64, seed: u64, size: usize, release: bool) {
+    let day = puzzle.day;
+
+    if let Err(e) = child_commands::build_solutions(&[puzzle], release) {
+        eprintln!("Failed to build solution for {puzzle}: {e}");
+        process::exit(1);
+    }
+
+    let variants = match child_commands::list_variants(puzzle, release) {
+        Ok(variants) => variants,
+        Err(e) => {
+            eprintln!("Failed to list variants for {puzzle}: {e}");
+            process::exit(1);
+        }
+    };
+
+    if variants.len() < 2 {
+        println!("Day {day} has a single variant, nothing to compare.");
+        return;
+    }
+
+    let inputs = collect_inputs(puzzle, generated, seed, size);
+    let mut failures = 0;
+
+    for (label, path) in &inputs {
+        let results = match variants::run_all(puzzle, &variants, path, release) {
+            Ok(results) => results,
+            Err(e) => {
+                failures += 1;
+                println!("{label}: a variant failed to run ({e}).");
+                continue;
+            }
+        };
+
+        for disagreement in variants::find_disagreements(&results) {
+            failures += 1;
+            println!("{label}: variants disagree on part {}.", disagreement.part);
+            for (variant, answer) in disagreement.answers {
+                println!("  {variant}: {answer}");
+            }
+        }
+    }
+
+    // only generated inputs are temporary, the real input and the examples are read in place.
+    for (_, path) in &inputs {
+        if path.starts_with(generated_dir()) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    println!("---");
+    println!(
+        "{ANSI_BOLD}Day {day}:{ANSI_RESET} compared {} variants ({}) on {} inputs, found {failures} problems.",
+        variants.len(),
+        variants.join(", "),
+        inputs.len()
+    );
+
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+/// Collect the real input, every example and a number of generated inputs for a puzzle.
+fn collect_inputs(
+    puzzle: PuzzleId,
+    generated: u64,
+    seed: u64,
+    size: usize,
+) -> Vec<(String, PathBuf)> {
+    let day = puzzle.day;
+    let mut inputs: Vec<(String, PathBuf)> = vec![];
+    let examples_dir = puzzle.year.data_dir().join("examples");
+
+    let candidates = [
+        ("input".to_string(), puzzle.input_path()),
+        ("example".to_string(), puzzle.example_path()),
+    ]
+    .into_iter()
+    .chain((1..=9).map(|part| {
+        (
+            format!("example {part}"),
+            examples_dir.join(format!("{day}-{part}.txt")),
+        )
+    }));
+
+    for (label, path) in candidates {
+        if is_non_empty_file(&path) {
+            inputs.push((label, path));
+        }
+    }
+
+    if let Some(generator) = generators::get_generator(puzzle) {
+        let tmp_dir = generated_dir();
+        if let Err(e) = fs::create_dir_all(&tmp_dir) {
+            eprintln!("Failed to create directory for generated inputs: {e}");
+            process::exit(1);
+        }
+
+        for run_seed in (0..generated).map(|i| seed.wrapping_add(i)) {
+            let path = tmp_dir.join(format!("{day}-{run_seed}.txt"));
+            let input = generator(&mut generators::Rng::new(run_seed), size);
+
+            if let Err(e) = fs::write(&path, input) {
+                eprintln!("Failed to write generated input: {e}");
+                process::exit(1);
+            }
+
+            inputs.push((format!("generated (seed {run_seed})"), path));
+        }
+    }
+
+    inputs
+}
+
+fn generated_dir() -> PathBuf {
+    env::temp_dir().join("advent_of_code_diff")
+}
+
+fn is_non_empty_file(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.len() > 0)
+}
@@ -1,14 +1,127 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::config::Config;
+use crate::template::http::Client;
+use crate::template::inputs::{self, InvalidInput, Manifest};
+use crate::template::{all_days, aoc_cli, DaySelection, PuzzleId};
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Download the input and description of a puzzle. A valid input that already exists is only downloaded again
+/// with `force`, and a download that is not a valid input never replaces an existing file.
+///
+/// # Errors
+/// Returns an error if the input could not be downloaded, was not valid, or could not be written.
+pub fn handle(config: &Config, puzzle: PuzzleId, force: bool) -> Result<(), String> {
+    let input_path = puzzle.input_path();
+    let mut manifest = Manifest::read_from_file(puzzle.year).map_err(|e| e.to_string())?;
+
+    if let Ok(existing) = fs::read_to_string(&input_path) {
+        match inputs::validate(&existing) {
+            Ok(_) if !force => {
+                let checksum = inputs::checksum(&existing);
+                match manifest.get(puzzle.day) {
+                    Some(previous) if previous != checksum => {
+                        println!("⚠️ The input of {puzzle} was changed since it was downloaded.");
+                    }
+                    Some(_) => {}
+                    None => {
+                        // inputs that were downloaded before the manifest existed.
+                        manifest.record(puzzle.day, checksum);
+                        store_manifest(&manifest, puzzle);
+                    }
+                }
+                println!(
+                    "🎄 Input already exists at \"{}\", skipping. Use `--force` to download it again.",
+                    input_path.display()
+                );
+                if !puzzle.puzzle_path().exists() {
+                    download_puzzle(puzzle);
+                }
+                return Ok(());
+            }
+            // `scaffold` creates empty inputs.
+            Ok(_) | Err(InvalidInput::Empty) => {}
+            Err(e) => println!(
+                "The existing input at \"{}\" is not valid, {e} Downloading it again.",
+                input_path.display()
+            ),
+        }
+    }
+
+    let downloaded = Client::new(config)
+        .fetch_input(puzzle, force)
+        .map_err(|e| format!("Failed to download the input of {puzzle}: {e}"))?;
+
+    let input = inputs::validate(&downloaded).map_err(|e| {
+        format!("The downloaded input of {puzzle} is not valid, {e} Keeping the existing input.")
+    })?;
+
+    let checksum = inputs::checksum(&input);
+    if manifest
+        .get(puzzle.day)
+        .is_some_and(|previous| previous != checksum)
+    {
+        println!("⚠️ The input of {puzzle} is different from the one that was downloaded before.");
+    }
+
+    let written = input_path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&input_path, &input));
+    if let Err(e) = written {
+        return Err(format!("Failed to write \"{}\": {e}", input_path.display()));
+    }
+
+    manifest.record(puzzle.day, checksum);
+    store_manifest(&manifest, puzzle);
+
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+
+    download_puzzle(puzzle);
+    Ok(())
+}
+
+/// Download the inputs of every selected day, in ascending order. A failed download does not stop the
+/// remaining days, the command fails after all of them were attempted.
+pub fn handle_selection(config: &Config, selection: &DaySelection, force: bool) {
+    let days = match selection.resolve() {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let mut failed = vec![];
+    for day in all_days().filter(|day| days.contains(day)) {
+        let puzzle = PuzzleId::new(selection.year, day);
+        if let Err(e) = handle(config, puzzle, force) {
+            eprintln!("{e}");
+            failed.push(puzzle.to_string());
+        }
+    }
+
+    if !failed.is_empty() {
+        eprintln!("Failed to download: {}.", failed.join(", "));
         process::exit(1);
     }
+}
+
+/// Download the description of a puzzle with aoc-cli, which converts it to markdown.
+fn download_puzzle(puzzle: PuzzleId) {
+    if aoc_cli::check().is_err() {
+        println!("Skipping the puzzle description, downloading it requires aoc-cli. Try running \"cargo install aoc-cli\" to install it.");
+        return;
+    }
 
-    if let Err(e) = aoc_cli::download(day) {
+    if let Err(e) = aoc_cli::download_puzzle(puzzle) {
         eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    }
+}
+
+fn store_manifest(manifest: &Manifest, puzzle: PuzzleId) {
+    if let Err(e) = manifest.store_file(puzzle.year) {
+        eprintln!("Failed to store the input checksum: {e}");
+    }
 }
@@ -0,0 +1,65 @@
+use std::path::Path;
+use std::time::Duration;
+use std::{fs, process};
+
+use crate::template::config::Config;
+use crate::template::http::Client;
+use crate::template::leaderboard::{self, Leaderboard};
+use crate::template::{Day, Year};
+
+/// The site asks to request private leaderboards at most once every 15 minutes.
+const MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// Where a leaderboard is read from.
+#[derive(Debug)]
+pub enum Source {
+    File(String),
+    /// The id of a private leaderboard, which is the user id of its owner.
+    Id(u64),
+}
+
+/// Print the ranking of a private leaderboard and the results of a day, by default of the latest day that anyone
+/// collected a star for.
+pub fn handle(config: &Config, year: Year, source: Option<Source>, day: Option<Day>) {
+    let json = match read_json(config, year, source) {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let leaderboard = match Leaderboard::try_from(json) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("Could not parse the leaderboard: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("{}", leaderboard::format_ranking(&leaderboard));
+
+    if let Some(day) = day.or_else(|| leaderboard.latest_day()) {
+        println!();
+        println!("{}", leaderboard::format_day(&leaderboard, day));
+    }
+}
+
+fn read_json(config: &Config, year: Year, source: Option<Source>) -> Result<String, String> {
+    let source = source
+        .or_else(|| config.leaderboard_id.map(Source::Id))
+        .ok_or(
+            "Pass the id of a private leaderboard, `--file <path>` or set `site.leaderboard_id` in `aoc.toml`.",
+        )?;
+
+    match source {
+        Source::File(path) => fs::read_to_string(Path::new(&path))
+            .map_err(|e| format!("Could not read \"{path}\": {e}")),
+        Source::Id(id) => Client::new(config)
+            .get(
+                &format!("/{year}/leaderboard/private/view/{id}.json"),
+                Some(MAX_AGE),
+            )
+            .map_err(|e| format!("Failed to fetch leaderboard {id}: {e}")),
+    }
+}
@@ -1,6 +1,14 @@
 pub mod all;
+pub mod bench;
+pub mod compare;
+pub mod countdown;
+pub mod diff;
 pub mod download;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
+pub mod scale;
 pub mod solve;
+pub mod stars;
+pub mod stress;
 pub mod time;
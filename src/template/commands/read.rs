@@ -1,15 +1,43 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::markdown;
+use crate::template::{aoc_cli, PuzzleId};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Render the stored description of a puzzle. The description is downloaded if it is missing, or with `--refresh`,
+/// e.g. once part two is unlocked.
+pub fn handle(puzzle: PuzzleId, part: Option<u8>, refresh: bool) {
+    let path = puzzle.puzzle_path();
+
+    if refresh || !path.exists() {
+        if aoc_cli::check().is_err() {
+            eprintln!(
+                "Downloading the description of {puzzle} requires aoc-cli. Try running \"cargo install aoc-cli\" to install it."
+            );
+            process::exit(1);
+        }
+
+        if let Err(e) = aoc_cli::download_puzzle(puzzle) {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        }
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let description = match fs::read_to_string(&path) {
+        Ok(description) => description,
+        Err(e) => {
+            eprintln!("Could not read \"{}\": {e}", path.display());
+            process::exit(1);
+        }
     };
+
+    match markdown::render(&description, part, markdown::terminal_width()) {
+        Some(text) => print!("{text}"),
+        None => {
+            eprintln!(
+                "The description of {puzzle} does not contain part two yet. Solve part one and run `cargo read {} --refresh`.",
+                puzzle.day
+            );
+            process::exit(1);
+        }
+    }
 }
@@ -1,10 +1,12 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::config::Config;
+use crate::template::PuzzleId;
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -14,13 +16,48 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Read the solution template of the config, falling back to the built-in template.
+fn read_template(config: &Config) -> String {
+    let Some(path) = &config.scaffold_template else {
+        return MODULE_TEMPLATE.to_string();
+    };
+
+    match fs::read_to_string(path) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!(
+                "Failed to read the solution template \"{}\": {e}",
+                path.display()
+            );
+            process::exit(1);
+        }
+    }
+}
+
+pub fn handle(config: &Config, puzzle: PuzzleId) {
+    let day = puzzle.day;
+    let input_path = puzzle.input_path().to_string_lossy().to_string();
+    let example_path = puzzle.example_path().to_string_lossy().to_string();
+    let module_path = puzzle.bin_path();
+
+    // other years are passed to the macro, so the solution reads its data files from the right folder.
+    let solution_args = if puzzle.year.is_primary() {
+        day.into_inner().to_string()
+    } else {
+        format!("year = {}, {}", puzzle.year, day.into_inner())
+    };
+
+    let template = read_template(config);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -30,11 +67,7 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(template.replace("%DAY_NUMBER%", &solution_args).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -65,5 +98,12 @@ pub fn handle(day: Day) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if puzzle.year.is_primary() {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!(
+            "🎄 Type `cargo solve {day} --year {}` to run your solution.",
+            puzzle.year
+        );
+    }
 }
@@ -0,0 +1,86 @@
+use std::{process, thread};
+
+use crate::template::readme_benchmarks::format_nanos;
+use crate::template::run_multi::{child_commands, RunOptions};
+use crate::template::{Limits, PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+/// Time a day with 1, 2, 4, … threads up to `max_threads` and report how well it scales.
+/// Defaults to the number of available cores.
+pub fn handle(puzzle: PuzzleId, max_threads: Option<usize>, limits: &Limits) {
+    let max_threads = max_threads
+        .or_else(|| thread::available_parallelism().ok().map(usize::from))
+        .unwrap_or(1);
+
+    if let Err(e) = child_commands::build_solutions(&[puzzle], true) {
+        eprintln!("Failed to build solution for {puzzle}: {e}");
+        process::exit(1);
+    }
+
+    println!("{ANSI_BOLD}Day {} scaling{ANSI_RESET}", puzzle.day);
+    println!("------");
+    println!(
+        "{:>7}  {:>10}  {:>7}  {:>10}",
+        "Threads", "Time", "Speedup", "Efficiency"
+    );
+
+    let mut baseline: Option<f64> = None;
+
+    for threads in thread_counts(max_threads) {
+        let options = RunOptions {
+            is_release: true,
+            is_timed: true,
+            threads: Some(threads),
+            ..RunOptions::default()
+        };
+
+        let output = match child_commands::run_solution(puzzle, &options, limits, false) {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Failed to run {puzzle}: {e}");
+                process::exit(1);
+            }
+        };
+
+        if output.timed_out {
+            println!("{threads:>7}  {:>10}", "TIMEOUT");
+            break;
+        }
+
+        let nanos = child_commands::parse_exec_time(&output.lines, puzzle.day).total_nanos;
+
+        if nanos <= 0.0 {
+            for line in &output.stderr {
+                eprintln!("{line}");
+            }
+            eprintln!("{puzzle} did not report any timings with {threads} threads.");
+            process::exit(1);
+        }
+
+        let baseline = *baseline.get_or_insert(nanos);
+        let (speedup, efficiency) = scaling(baseline, nanos, threads);
+
+        println!(
+            "{threads:>7}  {:>10}  {:>6.2}×  {:>9.1}%",
+            format_nanos(nanos),
+            speedup,
+            efficiency * 100.0
+        );
+    }
+}
+
+/// Powers of two below `max`, followed by `max` itself.
+fn thread_counts(max: usize) -> Vec<usize> {
+    let mut counts: Vec<usize> = (0..)
+        .map(|exp| 1 << exp)
+        .take_while(|threads| *threads < max)
+        .collect();
+    counts.push(max.max(1));
+    counts
+}
+
+/// Speedup relative to a single thread, and the share of it that each thread contributes.
+#[allow(clippy::cast_precision_loss)]
+fn scaling(baseline_nanos: f64, nanos: f64, threads: usize) -> (f64, f64) {
+    let speedup = baseline_nanos / nanos;
+    (speedup, speedup / threads as f64)
+}
@@ -1,34 +1,124 @@
-use std::process::{Command, Stdio};
-
-use crate::template::Day;
-
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
-
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+use std::io::{BufRead, BufReader};
+use std::process::{self, Command, Stdio};
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::child_commands;
+use crate::template::submission::{self, SubmitOptions};
+use crate::template::{all_days, DaySelection, PuzzleId};
+
+pub fn handle(
+    selection: &DaySelection,
+    release: bool,
+    dhat: bool,
+    submit: Option<SubmitOptions>,
+    threads: Option<usize>,
+) {
+    let days = match selection.resolve() {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if submit.is_some() && days.len() > 1 {
+        eprintln!("`--submit` can only be used when solving a single day.");
+        process::exit(1);
     }
 
-    cmd_args.push("--".to_string());
+    for day in all_days().filter(|day| days.contains(day)) {
+        let puzzle = PuzzleId::new(selection.year, day);
+        let bin_name = puzzle.bin_name();
+        let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name];
+
+        if dhat {
+            cmd_args.extend([
+                "--profile".to_string(),
+                "dhat".to_string(),
+                "--features".to_string(),
+                "dhat-heap".to_string(),
+            ]);
+        } else if release {
+            cmd_args.push("--release".to_string());
+        }
+
+        cmd_args.push("--".to_string());
+
+        if let Some(part) = selection.part {
+            cmd_args.push("--part".to_string());
+            cmd_args.push(part.to_string());
+        }
+
+        if let Some(threads) = threads {
+            cmd_args.push("--threads".to_string());
+            cmd_args.push(threads.to_string());
+        }
+
+        let Some(options) = submit else {
+            let mut cmd = Command::new("cargo")
+                .args(&cmd_args)
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit())
+                .spawn()
+                .unwrap();
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+            cmd.wait().unwrap();
+            continue;
+        };
+
+        let answers = Answers::read_from_file(puzzle.year).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+        let part = match submission::part_to_submit(&options, puzzle, &answers) {
+            Ok(Some(part)) => part,
+            Ok(None) => {
+                println!("All parts of {puzzle} have been accepted already.");
+                return;
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+        let lines = run_echoed(&cmd_args);
+        let Some((_, answer)) = child_commands::parse_answers(&lines)
+            .into_iter()
+            .find(|(p, answer)| *p == part && !answer.is_empty())
+        else {
+            eprintln!("Part {part} of {puzzle} did not produce an answer to submit.");
+            process::exit(1);
+        };
+
+        if let Err(e) = submission::submit(puzzle, part, &answer, &options, release) {
+            eprintln!("{e}");
+            process::exit(1);
+        }
     }
+}
 
+/// Run a solution, printing its output while collecting the lines to extract answers from.
+fn run_echoed(cmd_args: &[String]) -> Vec<String> {
     let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
+        .args(cmd_args)
+        .stdout(Stdio::piped())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let mut lines = vec![];
+    if let Some(stdout) = cmd.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            println!("{line}");
+            lines.push(line);
+        }
+    }
+
+    if !cmd.wait().unwrap().success() {
+        eprintln!("The solution did not exit successfully, not submitting.");
+        process::exit(1);
+    }
+
+    lines
 }
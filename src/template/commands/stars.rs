@@ -0,0 +1,54 @@
+use std::{fs, path::Path, process};
+
+use crate::template::answers::{self, Answers};
+use crate::template::{readme_stars, Year};
+
+pub fn handle(year: Year, calendar: Option<&Path>) {
+    let stars = match calendar {
+        Some(path) => {
+            let html = match fs::read_to_string(path) {
+                Ok(html) => html,
+                Err(e) => {
+                    eprintln!("Failed to read calendar page \"{}\": {e}", path.display());
+                    process::exit(1);
+                }
+            };
+
+            match readme_stars::from_calendar(&html) {
+                Ok(stars) => stars,
+                Err(e) => {
+                    eprintln!("Failed to parse calendar page: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+        None => {
+            // without recorded answers, the stars of the existing table are all there is.
+            let path = answers::get_path(year);
+            if !path.exists() {
+                eprintln!(
+                    "No answers are recorded in \"{}\" yet, leaving the stars table unchanged. Use `--calendar` to read the stars from the calendar page instead.",
+                    path.display()
+                );
+                process::exit(1);
+            }
+
+            match Answers::read_from_file(year) {
+                Ok(answers) => readme_stars::from_answers(&answers, year),
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            }
+        }
+    };
+
+    let total: u32 = match readme_stars::update(year, &stars) {
+        Ok(stars) => stars.values().map(|count| u32::from(*count)).sum(),
+        Err(e) => {
+            eprintln!("Failed to update stars table: {e}");
+            process::exit(1);
+        }
+    };
+    println!("Updated stars table for {year}: {total} ⭐");
+}
@@ -0,0 +1,148 @@
+use std::{
+    env, fs,
+    io::Read,
+    path::Path,
+    process::{self, Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use crate::generators;
+use crate::template::run_multi::child_commands;
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+/// Outcome of running a solution against a single generated input.
+enum Outcome {
+    Passed,
+    Panicked(String),
+    Overflowed(String),
+    TimedOut,
+}
+
+pub fn handle(
+    puzzle: PuzzleId,
+    seed: u64,
+    size: usize,
+    runs: u64,
+    timeout: Duration,
+    release: bool,
+) {
+    let day = puzzle.day;
+
+    let Some(generator) = generators::get_generator(puzzle) else {
+        eprintln!("There is no input generator for {puzzle}.");
+        process::exit(1);
+    };
+
+    if let Err(e) = child_commands::build_solutions(&[puzzle], release) {
+        eprintln!("Failed to build solution for {puzzle}: {e}");
+        process::exit(1);
+    }
+
+    let tmp_dir = env::temp_dir().join("advent_of_code_stress");
+    if let Err(e) = fs::create_dir_all(&tmp_dir) {
+        eprintln!("Failed to create directory for generated inputs: {e}");
+        process::exit(1);
+    }
+
+    let mut failures = 0;
+
+    for run_seed in (0..runs).map(|i| seed.wrapping_add(i)) {
+        let input = generator(&mut generators::Rng::new(run_seed), size);
+        let input_path = tmp_dir.join(format!("{day}-{run_seed}.txt"));
+
+        if let Err(e) = fs::write(&input_path, &input) {
+            eprintln!("Failed to write generated input: {e}");
+            process::exit(1);
+        }
+
+        let outcome = run_with_input(puzzle, &input_path, timeout, release);
+        // failing inputs are saved to the data folder below.
+        let _ = fs::remove_file(&input_path);
+
+        let outcome = match outcome {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                eprintln!("Failed to run solution for {puzzle}: {e}");
+                process::exit(1);
+            }
+        };
+
+        let message = match outcome {
+            Outcome::Passed => continue,
+            Outcome::Panicked(msg) => format!("panicked: {msg}"),
+            Outcome::Overflowed(msg) => format!("overflowed: {msg}"),
+            Outcome::TimedOut => format!("timed out after {timeout:?}"),
+        };
+
+        failures += 1;
+        let saved = save_failing_input(puzzle, run_seed, &input);
+        println!("Seed {run_seed}: {message} (input saved to \"{saved}\")");
+    }
+
+    println!("---");
+    println!("{ANSI_BOLD}Day {day}:{ANSI_RESET} {failures} of {runs} generated inputs failed.");
+
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+fn run_with_input(
+    puzzle: PuzzleId,
+    input_path: &Path,
+    timeout: Duration,
+    release: bool,
+) -> std::io::Result<Outcome> {
+    let mut child = Command::new(child_commands::get_target_path(puzzle, release))
+        .arg("--input")
+        .arg(input_path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // drain stderr concurrently, so a chatty child cannot block on a full pipe.
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let reader = thread::spawn(move || {
+        let mut s = String::new();
+        let _ = stderr.read_to_string(&mut s);
+        s
+    });
+
+    let status = child_commands::wait_with_timeout(&mut child, timeout)?;
+    let stderr = reader.join().unwrap_or_default();
+
+    let outcome = match status {
+        None => Outcome::TimedOut,
+        Some(status) if status.success() => Outcome::Passed,
+        Some(_) => {
+            // the panic message is printed on the line after the location of the panic.
+            let message = stderr
+                .lines()
+                .skip_while(|l| !l.contains("panicked at"))
+                .nth(1)
+                .unwrap_or("exited with a non-zero status")
+                .trim()
+                .to_string();
+
+            if message.contains("overflow") {
+                Outcome::Overflowed(message)
+            } else {
+                Outcome::Panicked(message)
+            }
+        }
+    };
+
+    Ok(outcome)
+}
+
+fn save_failing_input(puzzle: PuzzleId, seed: u64, input: &str) -> String {
+    let dir = puzzle.year.data_dir().join("generated");
+    let path = dir.join(format!("{}-{seed}.txt", puzzle.day));
+
+    if let Err(e) = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, input)) {
+        eprintln!("Failed to save failing input: {e}");
+    }
+
+    path.to_string_lossy().to_string()
+}
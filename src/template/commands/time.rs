@@ -1,39 +1,148 @@
-use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process;
 
-use crate::template::run_multi::run_multi;
+use crate::template::answers::Answers;
+use crate::template::config::Config;
+use crate::template::history::History;
+use crate::template::host::{self, Host};
+use crate::template::report::{self, Report};
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::runner::BenchConfig;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{charts, readme_benchmarks, variants, DaySelection, Limits, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Flags of the `time` command.
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Also time days that are already fully benched.
+    pub run_all: bool,
+    /// Store the timings and update the readme.
+    pub store: bool,
+    pub compare_variants: bool,
+    /// Count the heap allocations of each part.
+    pub track_allocations: bool,
+    /// Name of the benchmark table, see [`readme_benchmarks::update`].
+    pub table: Option<String>,
+    /// Write an HTML report to this path.
+    pub report: Option<PathBuf>,
+    pub mask_answers: bool,
+    /// Size of the rayon thread pool of each solution.
+    pub threads: Option<usize>,
+    pub bench: BenchConfig,
+}
+
+pub fn handle(
+    config: &Config,
+    selection: Option<DaySelection>,
+    year: Year,
+    limits: &Limits,
+    options: &Options,
+) {
+    let year = selection.as_ref().map_or(year, |s| s.year);
+    let table = options.table.as_deref();
+    let stored_timings = match Timings::read_from_file(year, table) {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+    let mut history = match History::read_from_file(year, table) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
 
-    let days_to_run = day.map_or_else(
+    let days_to_run = selection.as_ref().map_or_else(
         || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
+            let mut selection = DaySelection::all();
+            selection.year = year;
+            // when the `--all` flag is not set, filter out days that are fully benched.
+            selection.unsolved = !options.run_all;
+            selection.resolve_with(&stored_timings)
         },
-        |day| HashSet::from([day]),
+        |selection| selection.resolve_with(&stored_timings),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let part = selection.as_ref().and_then(|s| s.part);
 
-    if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+    if options.store && part.is_some() {
+        eprintln!("`--store` can not be combined with `--part`, since stored timings cover all parts of a day.");
+        process::exit(1);
+    }
+
+    if options.compare_variants {
+        if let Err(e) = variants::bench(&days_to_run, year) {
+            eprintln!("Failed to bench variants: {e}");
+        }
+        return;
+    }
+
+    let run_options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        track_allocations: options.track_allocations,
+        part,
+        threads: options.threads,
+        bench: options.bench,
+        ..RunOptions::default()
+    };
+
+    let timings = run_multi(&days_to_run, year, limits, &run_options).unwrap();
+    let merged_timings = stored_timings.merge(&timings);
+
+    if options.store {
+        merged_timings.store_file(year, table).unwrap();
+
+        history.record(host::now(), &timings);
+        if let Err(e) = history.store_file(year, table) {
+            eprintln!("Failed to store benchmark history: {e}");
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match charts::update(&merged_timings, year, table) {
+            Ok(path) => {
+                println!("Rendered benchmark chart to `{}`.", path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to render benchmark chart: {e}");
+            }
+        }
+
+        match readme_benchmarks::update(merged_timings.clone(), year, table, &config.readme_options)
+        {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
+            }
+        }
+    }
+
+    if let Some(path) = &options.report {
+        let report = Report {
+            year,
+            timings: &merged_timings,
+            answers: &Answers::read_from_file(year).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            }),
+            history: &history,
+            host: &Host::detect(),
+            generated_at: host::now(),
+            mask_answers: options.mask_answers,
+            source_prefix: report::source_prefix(path),
+        };
+
+        match report::write(path, &report) {
+            Ok(()) => {
+                println!("Wrote report to `{}`.", path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to write report: {e}");
             }
         }
     }
@@ -0,0 +1,455 @@
+/// Module that loads the project configuration from `aoc.toml`. Every setting can be overridden by an environment
+/// variable, e.g. `AOC_YEAR` for `year`.
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, fs};
+
+use crate::template::readme_benchmarks::TableOptions;
+use crate::template::runner::BenchConfig;
+use crate::template::Year;
+
+static CONFIG_FILE_NAME: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Keys of the configuration file, with the environment variables that override them.
+const KEYS: [(&str, &str); 13] = [
+    ("year", "AOC_YEAR"),
+    ("data_dir", "AOC_DATA_DIR"),
+    ("site.session_file", "AOC_SESSION_FILE"),
+    ("site.user_agent", "AOC_USER_AGENT"),
+    ("site.leaderboard_id", "AOC_LEADERBOARD_ID"),
+    ("bench.warm_up", "AOC_WARM_UP"),
+    ("bench.time", "AOC_BENCH_TIME"),
+    ("bench.precision", "AOC_PRECISION"),
+    ("limits.timeout", "AOC_TIMEOUT"),
+    ("limits.memory_limit", "AOC_MEMORY_LIMIT"),
+    ("readme.table", "AOC_README_TABLE"),
+    ("readme.options", "AOC_README_OPTIONS"),
+    ("scaffold.template", "AOC_SCAFFOLD_TEMPLATE"),
+];
+
+/// Settings of the template.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// The primary year, see [`Year::primary`]. Defaults to the current year.
+    pub year: Option<Year>,
+    /// Directory of the inputs, examples, puzzles and timings of the primary year. Other years are stored in
+    /// sub-directories named after the year.
+    pub data_dir: PathBuf,
+    /// File with the session cookie of the puzzle site. `ADVENT_OF_CODE_SESSION` takes precedence.
+    pub session_file: Option<PathBuf>,
+    /// Contact details that are added to the `User-Agent` of requests to the puzzle site.
+    pub user_agent: Option<String>,
+    /// Id of the private leaderboard shown by the `leaderboard` command.
+    pub leaderboard_id: Option<u64>,
+    pub bench: BenchConfig,
+    /// Timeout of each solution that is run by `all` and `time`.
+    pub timeout: Option<Duration>,
+    pub memory_limit_mb: Option<u64>,
+    /// Benchmark table that `time --store` updates.
+    pub readme_table: Option<String>,
+    /// Layout of benchmark tables that do not have an options comment.
+    pub(crate) readme_options: TableOptions,
+    /// Template of new solutions. Defaults to the built-in `src/template.txt`.
+    pub scaffold_template: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: None,
+            data_dir: PathBuf::from("data"),
+            session_file: home_dir().map(|home| home.join(".adventofcode.session")),
+            user_agent: None,
+            leaderboard_id: None,
+            bench: BenchConfig::default(),
+            timeout: None,
+            memory_limit_mb: None,
+            readme_table: None,
+            readme_options: TableOptions::default(),
+            scaffold_template: None,
+        }
+    }
+}
+
+impl Config {
+    /// Load `aoc.toml` from the current directory, if it exists, and apply the overrides of the environment.
+    pub fn load() -> Result<Self, String> {
+        let toml = match fs::read_to_string(CONFIG_FILE_NAME) {
+            Ok(toml) => toml,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Could not read {CONFIG_FILE_NAME}: {e}")),
+        };
+
+        Self::from_toml(&toml, |name| env::var(name).ok())
+            .map_err(|e| format!("Invalid {CONFIG_FILE_NAME}: {e}"))
+    }
+
+    /// Build the configuration from the contents of a config file and a lookup of environment variables.
+    pub fn from_toml(toml: &str, env: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let mut values = parse_toml(toml)?;
+
+        if let Some(key) = values
+            .keys()
+            .find(|key| !KEYS.iter().any(|(k, _)| k == key))
+        {
+            return Err(format!("unknown key `{key}`."));
+        }
+
+        for (key, var) in KEYS {
+            if let Some(value) = env(var).filter(|v| !v.is_empty()) {
+                values.insert(key.into(), Value::String(value));
+            }
+        }
+
+        let values = Values(values);
+        let default = Config::default();
+        let millis = |key| Ok::<_, String>(values.parse::<u64>(key)?.map(Duration::from_millis));
+
+        Ok(Config {
+            year: values.parse("year")?,
+            data_dir: values.parse("data_dir")?.unwrap_or(default.data_dir),
+            session_file: values
+                .parse::<String>("site.session_file")?
+                .map(|path| expand_home(&path))
+                .or(default.session_file),
+            user_agent: values.parse("site.user_agent")?,
+            leaderboard_id: values.parse("site.leaderboard_id")?,
+            bench: BenchConfig {
+                warm_up: millis("bench.warm_up")?.unwrap_or(default.bench.warm_up),
+                budget: millis("bench.time")?.unwrap_or(default.bench.budget),
+                precision: values
+                    .parse::<f64>("bench.precision")?
+                    .map_or(default.bench.precision, |percent| percent / 100.0),
+            },
+            timeout: values
+                .parse::<u64>("limits.timeout")?
+                .map(Duration::from_secs),
+            memory_limit_mb: values.parse("limits.memory_limit")?,
+            readme_table: values.parse("readme.table")?,
+            readme_options: values
+                .get("readme.options")
+                .map(|options| {
+                    format!("<!--- options: {} --->", options.as_string())
+                        .parse::<TableOptions>()
+                        .map_err(|e| format!("`readme.options`: {e}"))
+                })
+                .transpose()?
+                .unwrap_or_default(),
+            scaffold_template: values.parse("scaffold.template")?,
+        })
+    }
+}
+
+/// The configuration of this process, loaded on first use. An invalid config file is reported and ignored,
+/// see [`init`] to fail on it instead.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        Config::load().unwrap_or_else(|e| {
+            eprintln!("{e}");
+            Config::default()
+        })
+    })
+}
+
+/// Load the configuration of this process, failing if the config file is invalid.
+pub fn init() -> Result<&'static Config, String> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+    let config = Config::load()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl Value {
+    fn as_string(&self) -> String {
+        match self {
+            Value::String(s) => s.clone(),
+            Value::Integer(n) => n.to_string(),
+            Value::Float(n) => n.to_string(),
+            Value::Boolean(b) => b.to_string(),
+        }
+    }
+}
+
+struct Values(BTreeMap<String, Value>);
+
+impl Values {
+    fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    /// Parse a value, e.g. a number that was overridden by an environment variable.
+    fn parse<T: FromStr>(&self, key: &str) -> Result<Option<T>, String> {
+        self.get(key)
+            .map(|value| {
+                value
+                    .as_string()
+                    .parse()
+                    .map_err(|_| format!("invalid value `{}` of `{key}`.", value.as_string()))
+            })
+            .transpose()
+    }
+}
+
+/// Parse the subset of TOML that the config file uses: tables, and keys with strings, numbers and booleans.
+/// Returns the values by their dotted keys, e.g. `bench.warm_up`.
+fn parse_toml(toml: &str) -> Result<BTreeMap<String, Value>, String> {
+    let mut values = BTreeMap::new();
+    let mut table = String::new();
+
+    for (i, line) in toml.lines().enumerate() {
+        let err = |e: &str| format!("line {}: {e}", i + 1);
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .filter(|name| !name.starts_with('['))
+                .ok_or_else(|| err("expected a table like `[bench]`."))?
+                .trim();
+            if !is_bare_key(name) {
+                return Err(err("invalid table name."));
+            }
+            table = name.to_string();
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected `key = value`."))?;
+        let key = key.trim();
+        if !is_bare_key(key) {
+            return Err(err(&format!("invalid key `{key}`.")));
+        }
+
+        let key = if table.is_empty() {
+            key.to_string()
+        } else {
+            format!("{table}.{key}")
+        };
+        let value = parse_value(value.trim()).map_err(|e| err(&e))?;
+
+        if values.insert(key.clone(), value).is_some() {
+            return Err(err(&format!("duplicate key `{key}`.")));
+        }
+    }
+
+    Ok(values)
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Remove a trailing comment, ignoring `#` inside strings.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+
+    line
+}
+
+fn parse_value(s: &str) -> Result<Value, String> {
+    if let Some(rest) = s.strip_prefix('"') {
+        let body = rest.strip_suffix('"').ok_or("unterminated string.")?;
+        return unescape(body).map(Value::String);
+    }
+
+    if let Some(rest) = s.strip_prefix('\'') {
+        let body = rest.strip_suffix('\'').ok_or("unterminated string.")?;
+        return Ok(Value::String(body.to_string()));
+    }
+
+    match s {
+        "true" => return Ok(Value::Boolean(true)),
+        "false" => return Ok(Value::Boolean(false)),
+        _ => {}
+    }
+
+    let number = s.replace('_', "");
+    if let Ok(n) = number.parse::<i64>() {
+        return Ok(Value::Integer(n));
+    }
+    if number.contains(['.', 'e', 'E']) {
+        if let Ok(n) = number.parse::<f64>() {
+            return Ok(Value::Float(n));
+        }
+    }
+
+    Err(format!("invalid value `{s}`."))
+}
+
+fn unescape(s: &str) -> Result<String, String> {
+    let mut out = String::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                let c = u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid escape `\\u{hex}`."))?;
+                out.push(c);
+            }
+            Some(c) => return Err(format!("invalid escape `\\{c}`.")),
+            None => return Err("unterminated escape.".into()),
+        }
+    }
+
+    Ok(out)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{parse_toml, Config, Value};
+    use crate::template::readme_benchmarks::{SortOrder, TableOptions};
+
+    const TOML: &str = r#"
+# comment
+year = 2023
+data_dir = "puzzles" # trailing comment
+
+[site]
+user_agent = "me@example.com # not a comment"
+leaderboard_id = 3_031
+
+[bench]
+warm_up = 200
+precision = 0.5
+
+[readme]
+options = 'sort=cost highlight=3'
+"#;
+
+    #[test]
+    fn parses_toml() {
+        let values = parse_toml(TOML).unwrap();
+        assert_eq!(values.get("year"), Some(&Value::Integer(2023)));
+        assert_eq!(
+            values.get("site.user_agent"),
+            Some(&Value::String("me@example.com # not a comment".into()))
+        );
+        assert_eq!(values.get("bench.precision"), Some(&Value::Float(0.5)));
+        assert_eq!(
+            parse_toml(r#"a = "x\"é\n""#).unwrap().get("a"),
+            Some(&Value::String("x\"é\n".into()))
+        );
+
+        assert!(parse_toml("year").is_err());
+        assert!(parse_toml("year = 1\nyear = 2").is_err());
+        assert!(parse_toml("[[bench]]").is_err());
+        assert!(parse_toml("a = \"open").is_err());
+        assert!(parse_toml("a = 1a").is_err());
+    }
+
+    #[test]
+    fn builds_config() {
+        let config = Config::from_toml(TOML, |_| None).unwrap();
+        assert_eq!(config.year.map(|y| y.into_inner()), Some(2023));
+        assert_eq!(config.data_dir, PathBuf::from("puzzles"));
+        assert_eq!(config.leaderboard_id, Some(3031));
+        assert_eq!(config.bench.warm_up, Duration::from_millis(200));
+        assert_eq!(config.bench.budget, Duration::from_secs(1));
+        assert!((config.bench.precision - 0.005).abs() < f64::EPSILON);
+        assert_eq!(
+            config.readme_options,
+            TableOptions {
+                sort: SortOrder::Cost,
+                highlight: 3,
+                ..TableOptions::default()
+            }
+        );
+
+        let config = Config::from_toml("", |_| None).unwrap();
+        assert_eq!(config.data_dir, PathBuf::from("data"));
+        assert!(config.year.is_none());
+    }
+
+    #[test]
+    fn applies_env_overrides() {
+        let env = |name: &str| match name {
+            "AOC_YEAR" => Some("2021".to_string()),
+            "AOC_TIMEOUT" => Some("30".to_string()),
+            _ => None,
+        };
+        let config = Config::from_toml(TOML, env).unwrap();
+        assert_eq!(config.year.map(|y| y.into_inner()), Some(2021));
+        assert_eq!(config.timeout, Some(Duration::from_secs(30)));
+
+        let env = |name: &str| (name == "AOC_YEAR").then(|| "next year".to_string());
+        assert!(Config::from_toml("", env).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        assert!(Config::from_toml("yaer = 2023", |_| None).is_err());
+        assert!(Config::from_toml("year = 2014", |_| None).is_err());
+        assert!(Config::from_toml("[readme]\noptions = \"sort=size\"", |_| None).is_err());
+    }
+}
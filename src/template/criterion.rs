@@ -0,0 +1,427 @@
+/// Module that stores benchmark samples in the on-disk format of [criterion](https://github.com/bheisler/criterion.rs),
+/// so that baselines can be saved and compared like with a criterion benchmark.
+///
+/// Every benchmark has a directory `<group>/<function>` below the criterion home, with a sub-directory per baseline
+/// that holds `benchmark.json`, `estimates.json`, `sample.json` and `tukey.json`. The latest run is stored as `new`.
+/// Criterion estimates its confidence intervals by bootstrapping, here they are approximated from the normal
+/// distribution instead.
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{env, fs, io};
+
+use tinyjson::JsonValue;
+
+use crate::template::stats;
+
+/// Name of the baseline that holds the latest run.
+pub const NEW_BASELINE: &str = "new";
+
+/// Name of the baseline that runs are compared against and saved to by default.
+pub const DEFAULT_BASELINE: &str = "base";
+
+/// Relative changes of the mean within this threshold are considered noise, the same default as criterion's.
+pub const NOISE_THRESHOLD: f64 = 0.02;
+
+/// z-score of the 95% confidence level.
+const Z_95: f64 = 1.96;
+
+/// Directory of all benchmarks, `$CRITERION_HOME` or `target/criterion`.
+#[must_use]
+pub fn home() -> PathBuf {
+    if let Ok(home) = env::var("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+
+    env::var("CARGO_TARGET_DIR")
+        .map_or_else(|_| PathBuf::from("target"), PathBuf::from)
+        .join("criterion")
+}
+
+/// Samples of a single benchmark, with one iteration per sample.
+#[derive(Clone, Debug)]
+pub struct Measurement {
+    pub group_id: String,
+    pub function_id: String,
+    /// Run time of each sample in nanoseconds.
+    pub times: Vec<f64>,
+}
+
+impl Measurement {
+    #[must_use]
+    pub fn full_id(&self) -> String {
+        format!("{}/{}", self.group_id, self.function_id)
+    }
+
+    /// Directory of the benchmark, with the characters that criterion replaces in directory names replaced.
+    #[must_use]
+    pub fn dir(&self, home: &Path) -> PathBuf {
+        let sanitize =
+            |s: &str| s.replace(['?', '"', '/', '\\', '*', '<', '>', ':', '|', '^'], "_");
+        home.join(sanitize(&self.group_id))
+            .join(sanitize(&self.function_id))
+    }
+
+    fn sorted_times(&self) -> Vec<f64> {
+        let mut sorted = self.times.clone();
+        sorted.sort_unstable_by(f64::total_cmp);
+        sorted
+    }
+
+    /// Number of samples outside of the mild Tukey fences.
+    #[must_use]
+    pub fn outliers(&self) -> usize {
+        let sorted = self.sorted_times();
+        let [_, low_mild, high_mild, _] = stats::tukey_fences(&sorted);
+        sorted
+            .iter()
+            .filter(|x| **x < low_mild || **x > high_mild)
+            .count()
+    }
+}
+
+/// A point estimate along with its 95% confidence interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    pub point_estimate: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+    pub standard_error: f64,
+}
+
+impl Estimate {
+    fn normal(point_estimate: f64, standard_error: f64) -> Self {
+        Estimate {
+            point_estimate,
+            lower_bound: point_estimate - Z_95 * standard_error,
+            upper_bound: point_estimate + Z_95 * standard_error,
+            standard_error,
+        }
+    }
+}
+
+/// Estimates of the run time of a benchmark in nanoseconds, as stored in `estimates.json`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimates {
+    pub mean: Estimate,
+    pub median: Estimate,
+    pub median_abs_dev: Estimate,
+    pub std_dev: Estimate,
+}
+
+impl Estimates {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_measurement(measurement: &Measurement) -> Self {
+        let sorted = measurement.sorted_times();
+        let n = (sorted.len().max(1)) as f64;
+
+        let std_dev = stats::std_dev(&sorted);
+        let median_abs_dev = stats::median_abs_dev(&sorted);
+        // asymptotic standard errors of normally distributed samples.
+        let std_error = std_dev / n.sqrt();
+
+        Estimates {
+            mean: Estimate::normal(stats::mean(&sorted), std_error),
+            median: Estimate::normal(
+                stats::quantile(&sorted, 0.5),
+                std_error * (std::f64::consts::PI / 2.0).sqrt(),
+            ),
+            median_abs_dev: Estimate::normal(median_abs_dev, median_abs_dev / (2.0 * n).sqrt()),
+            std_dev: Estimate::normal(std_dev, std_dev / (2.0 * (n - 1.0).max(1.0)).sqrt()),
+        }
+    }
+}
+
+/// Outcome of comparing a benchmark to a baseline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Improved,
+    Regressed,
+    NoChange,
+}
+
+/// Relative change of the mean run time compared to a baseline, e.g. `-0.1` for a run that is 10% faster.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    pub lower_bound: f64,
+    pub point_estimate: f64,
+    pub upper_bound: f64,
+    pub verdict: Verdict,
+}
+
+/// Compare the estimates of a run to those of a baseline.
+/// A change is only reported if its whole confidence interval lies beyond the [`NOISE_THRESHOLD`].
+#[must_use]
+pub fn compare(base: &Estimates, new: &Estimates) -> Comparison {
+    let change = |new: f64, base: f64| if base > 0.0 { new / base - 1.0 } else { 0.0 };
+
+    let lower_bound = change(new.mean.lower_bound, base.mean.upper_bound);
+    let upper_bound = change(new.mean.upper_bound, base.mean.lower_bound.max(0.0));
+
+    let verdict = if lower_bound > NOISE_THRESHOLD {
+        Verdict::Regressed
+    } else if upper_bound < -NOISE_THRESHOLD {
+        Verdict::Improved
+    } else {
+        Verdict::NoChange
+    };
+
+    Comparison {
+        lower_bound,
+        point_estimate: change(new.mean.point_estimate, base.mean.point_estimate),
+        upper_bound,
+        verdict,
+    }
+}
+
+/// Write the files of a measurement to a baseline directory, replacing its previous contents.
+pub fn save(
+    home: &Path,
+    measurement: &Measurement,
+    estimates: &Estimates,
+    baseline: &str,
+) -> Result<(), io::Error> {
+    let dir = measurement.dir(home).join(baseline);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    fs::create_dir_all(&dir)?;
+
+    let sorted = measurement.sorted_times();
+    let files = [
+        ("benchmark.json", benchmark_json(measurement)),
+        ("estimates.json", JsonValue::from(estimates)),
+        ("sample.json", sample_json(measurement)),
+        (
+            "tukey.json",
+            JsonValue::Array(
+                stats::tukey_fences(&sorted)
+                    .into_iter()
+                    .map(JsonValue::Number)
+                    .collect(),
+            ),
+        ),
+    ];
+
+    for (name, json) in files {
+        let mut file = fs::File::create(dir.join(name))?;
+        json.format_to(&mut file)?;
+    }
+
+    Ok(())
+}
+
+/// Read the estimates of a baseline. Returns `None` if the benchmark has no such baseline.
+pub fn read_estimates(
+    home: &Path,
+    measurement: &Measurement,
+    baseline: &str,
+) -> Option<Result<Estimates, String>> {
+    let path = measurement.dir(home).join(baseline).join("estimates.json");
+
+    if !path.exists() {
+        return None;
+    }
+
+    Some(
+        fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| Estimates::try_from(s.as_str()))
+            .map_err(|e| format!("Could not read `{}`: {e}", path.display())),
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn benchmark_json(measurement: &Measurement) -> JsonValue {
+    let full_id = measurement.full_id();
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert(
+        "group_id".into(),
+        JsonValue::String(measurement.group_id.clone()),
+    );
+    map.insert(
+        "function_id".into(),
+        JsonValue::String(measurement.function_id.clone()),
+    );
+    map.insert("value_str".into(), JsonValue::Null);
+    map.insert("throughput".into(), JsonValue::Null);
+    map.insert("full_id".into(), JsonValue::String(full_id.clone()));
+    map.insert("directory_name".into(), JsonValue::String(full_id.clone()));
+    map.insert("title".into(), JsonValue::String(full_id));
+    JsonValue::Object(map)
+}
+
+fn sample_json(measurement: &Measurement) -> JsonValue {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("sampling_mode".into(), JsonValue::String("Flat".into()));
+    map.insert(
+        "iters".into(),
+        JsonValue::Array(vec![JsonValue::Number(1.0); measurement.times.len()]),
+    );
+    map.insert(
+        "times".into(),
+        JsonValue::Array(
+            measurement
+                .times
+                .iter()
+                .copied()
+                .map(JsonValue::Number)
+                .collect(),
+        ),
+    );
+    JsonValue::Object(map)
+}
+
+impl From<&Estimate> for JsonValue {
+    fn from(value: &Estimate) -> Self {
+        let mut interval: HashMap<String, JsonValue> = HashMap::new();
+        interval.insert("confidence_level".into(), JsonValue::Number(0.95));
+        interval.insert("lower_bound".into(), JsonValue::Number(value.lower_bound));
+        interval.insert("upper_bound".into(), JsonValue::Number(value.upper_bound));
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("confidence_interval".into(), JsonValue::Object(interval));
+        map.insert(
+            "point_estimate".into(),
+            JsonValue::Number(value.point_estimate),
+        );
+        map.insert(
+            "standard_error".into(),
+            JsonValue::Number(value.standard_error),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Estimates> for JsonValue {
+    fn from(value: &Estimates) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("mean".into(), JsonValue::from(&value.mean));
+        map.insert("median".into(), JsonValue::from(&value.median));
+        map.insert(
+            "median_abs_dev".into(),
+            JsonValue::from(&value.median_abs_dev),
+        );
+        // slopes are only estimated for linear sampling, which is not used.
+        map.insert("slope".into(), JsonValue::Null);
+        map.insert("std_dev".into(), JsonValue::from(&value.std_dev));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Estimate {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let err = || "expected an estimate object.".to_string();
+        let json = value.get::<HashMap<String, JsonValue>>().ok_or_else(err)?;
+        let interval = json
+            .get("confidence_interval")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or_else(err)?;
+        let number = |map: &HashMap<String, JsonValue>, key: &str| {
+            map.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or_else(err)
+        };
+
+        Ok(Estimate {
+            point_estimate: number(json, "point_estimate")?,
+            lower_bound: number(interval, "lower_bound")?,
+            upper_bound: number(interval, "upper_bound")?,
+            standard_error: number(json, "standard_error")?,
+        })
+    }
+}
+
+impl TryFrom<&str> for Estimates {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+        let estimate = |key: &str| {
+            json.get(key)
+                .ok_or(format!("expected JSON document to have key `{key}`."))
+                .and_then(Estimate::try_from)
+        };
+
+        Ok(Estimates {
+            mean: estimate("mean")?,
+            median: estimate("median")?,
+            median_abs_dev: estimate("median_abs_dev")?,
+            std_dev: estimate("std_dev")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{compare, Estimates, Measurement, Verdict};
+    use tinyjson::JsonValue;
+
+    fn measurement(times: &[f64]) -> Measurement {
+        Measurement {
+            group_id: "2023 day 01".into(),
+            function_id: "part 1".into(),
+            times: times.to_vec(),
+        }
+    }
+
+    fn estimates(mean: f64) -> Estimates {
+        let times: Vec<f64> = (0..100).map(|i| mean + f64::from(i % 5) - 2.0).collect();
+        Estimates::from_measurement(&measurement(&times))
+    }
+
+    #[test]
+    fn estimates_measurements() {
+        let estimates = estimates(100.0);
+        assert!((estimates.mean.point_estimate - 100.0).abs() < 1e-9);
+        assert!((estimates.median.point_estimate - 100.0).abs() < 1e-9);
+        assert!(estimates.mean.lower_bound < 100.0 && estimates.mean.upper_bound > 100.0);
+        assert!((estimates.std_dev.point_estimate - 1.4213).abs() < 1e-3);
+    }
+
+    #[test]
+    fn compares_estimates() {
+        assert_eq!(
+            compare(&estimates(100.0), &estimates(100.5)).verdict,
+            Verdict::NoChange
+        );
+        assert_eq!(
+            compare(&estimates(100.0), &estimates(120.0)).verdict,
+            Verdict::Regressed
+        );
+
+        let comparison = compare(&estimates(100.0), &estimates(50.0));
+        assert_eq!(comparison.verdict, Verdict::Improved);
+        assert!((comparison.point_estimate + 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn roundtrips_estimates() {
+        let estimates = estimates(1_000.0);
+        let json = JsonValue::from(&estimates).stringify().unwrap();
+        assert!(json.contains(r#""slope":null"#));
+        assert_eq!(Estimates::try_from(json.as_str()), Ok(estimates));
+    }
+
+    #[test]
+    fn sanitizes_directories() {
+        let mut measurement = measurement(&[1.0, 2.0, 3.0, 4.0, 50.0]);
+        measurement.group_id = "a/b:c".into();
+        assert_eq!(
+            measurement.dir(Path::new("home")),
+            Path::new("home/a_b_c/part 1")
+        );
+        assert_eq!(measurement.outliers(), 1);
+    }
+}
@@ -1,12 +1,18 @@
+use std::collections::{BTreeSet, HashSet};
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use super::timings::{self, Timings};
+use super::{PuzzleId, Year};
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, Local};
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
+/// Not every year has 25 days, see [`Year::day_count`](super::Year::day_count).
+///
 /// # Display
 /// This value displays as a two digit number.
 ///
@@ -42,10 +48,10 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's part of this year's advent calendar, `None` otherwise.
     pub fn today() -> Option<Self> {
         let today = Local::now();
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12 && today.day() <= u32::from(Year::current().day_count()) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -101,15 +107,24 @@ pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the 25th, or to an earlier last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(Day(25))
+    }
+
+    /// An iterator that yields every day from the 1st up to and including `last`.
+    pub fn until(last: Day) -> Self {
+        Self {
+            current: 1,
+            last: last.0,
+        }
     }
 }
 
@@ -117,10 +132,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and `last` is a valid day, so the value is not above 25.
         let day = Day(self.current);
         self.current += 1;
 
@@ -130,6 +145,145 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// Days whose stored timings exceed this duration are considered slow.
+pub const SLOW_THRESHOLD_MILLIS: f64 = 100.0;
+
+/// A selection of days and parts that a command operates on.
+///
+/// Days are parsed from a comma-separated list of days and inclusive ranges, e.g. `1-5,9,12`.
+/// The selection can be narrowed down further with filters that depend on the stored timings.
+///
+/// ```
+/// # use advent_of_code::template::{DaySelection, Day};
+/// let selection: DaySelection = "1-3,7".parse().unwrap();
+/// assert_eq!(selection.days().len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySelection {
+    days: BTreeSet<Day>,
+    /// The year the selected days belong to. Defaults to the primary year.
+    pub year: Year,
+    /// Only run this part of each selected day.
+    pub part: Option<u8>,
+    /// Only keep days whose stored timings exceed [`SLOW_THRESHOLD_MILLIS`].
+    pub slow_only: bool,
+    /// Only keep days that do not have stored timings for all of their parts.
+    pub unsolved: bool,
+}
+
+impl DaySelection {
+    /// Selects every day of advent. Days past the end of the selected year's calendar are dropped when
+    /// resolving the selection.
+    #[must_use]
+    pub fn all() -> Self {
+        Self::from_days(all_days())
+    }
+
+    /// Selects the provided days.
+    pub fn from_days(days: impl IntoIterator<Item = Day>) -> Self {
+        Self {
+            days: days.into_iter().collect(),
+            year: Year::primary(),
+            part: None,
+            slow_only: false,
+            unsolved: false,
+        }
+    }
+
+    /// The explicitly selected days in ascending order, before applying any filters.
+    #[must_use]
+    pub fn days(&self) -> Vec<Day> {
+        self.days.iter().copied().collect()
+    }
+
+    /// Whether the selection filters days based on stored timings.
+    #[must_use]
+    pub fn has_filters(&self) -> bool {
+        self.slow_only || self.unsolved
+    }
+
+    /// Applies the filters of this selection, returning the days that remain.
+    /// Stored timings are only read if a filter depends on them.
+    ///
+    /// # Errors
+    /// Returns an error if the stored timings are needed but could not be read.
+    pub fn resolve(&self) -> Result<HashSet<Day>, timings::Error> {
+        let timings = if self.has_filters() {
+            Timings::read_from_file(self.year, None)?
+        } else {
+            Timings::default()
+        };
+
+        Ok(self.resolve_with(&timings))
+    }
+
+    /// Applies the filters of this selection based on the provided timings.
+    pub(crate) fn resolve_with(&self, timings: &Timings) -> HashSet<Day> {
+        self.days
+            .iter()
+            .copied()
+            .filter(|day| self.year.has_day(*day))
+            .filter(|day| {
+                !self.unsolved || !timings.is_day_complete(PuzzleId::new(self.year, *day))
+            })
+            .filter(|day| {
+                !self.slow_only
+                    || timings.data.iter().any(|t| {
+                        t.day == *day && t.total_nanos / 1_000_000_f64 > SLOW_THRESHOLD_MILLIS
+                    })
+            })
+            .collect()
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = DaySelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeSet::new();
+
+        for item in s.split(',').map(str::trim) {
+            let err = || DaySelectionFromStrError(item.to_string());
+
+            match item.split_once('-') {
+                Some((start, end)) => {
+                    let start: Day = start.trim().parse().map_err(|_| err())?;
+                    let end: Day = end.trim().parse().map_err(|_| err())?;
+
+                    if start > end {
+                        return Err(err());
+                    }
+
+                    days.extend(all_days().filter(|d| *d >= start && *d <= end));
+                }
+                None => {
+                    days.insert(item.parse().map_err(|_| err())?);
+                }
+            }
+        }
+
+        Ok(Self::from_days(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelection`].
+#[derive(Debug)]
+pub struct DaySelectionFromStrError(String);
+
+impl Error for DaySelectionFromStrError {}
+
+impl Display for DaySelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting days between 1 and 25 or ranges like `1-5`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -150,7 +304,9 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySelection};
+    use crate::template::timings::{Timing, TimingStats, Timings};
+    use crate::template::Year;
 
     #[test]
     fn all_days_iterator() {
@@ -183,6 +339,70 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_selections() {
+        let selection: DaySelection = "1-3, 7,12".parse().unwrap();
+        assert_eq!(
+            selection.days(),
+            vec![Day(1), Day(2), Day(3), Day(7), Day(12)]
+        );
+
+        let selection: DaySelection = "5".parse().unwrap();
+        assert_eq!(selection.days(), vec![Day(5)]);
+    }
+
+    #[test]
+    fn rejects_invalid_day_selections() {
+        assert!("0-3".parse::<DaySelection>().is_err());
+        assert!("5-3".parse::<DaySelection>().is_err());
+        assert!("1,,2".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn filters_day_selections() {
+        let timing = |day: u8, part_2_nanos: Option<f64>, total_nanos: f64| Timing {
+            day: Day(day),
+            part_1_nanos: Some(1e6),
+            part_2_nanos,
+            total_nanos,
+            timed_out: false,
+            stats: TimingStats::default(),
+            meta: None,
+        };
+
+        let timings = Timings {
+            data: vec![
+                timing(1, Some(1e6), 2e6),
+                timing(2, None, 1e6),
+                timing(3, Some(1e9), 1e9),
+            ],
+        };
+
+        let mut selection: DaySelection = "1-4".parse().unwrap();
+        selection.unsolved = true;
+        let mut days: Vec<Day> = selection.resolve_with(&timings).into_iter().collect();
+        days.sort();
+        assert_eq!(days, vec![Day(2), Day(4)]);
+
+        let mut selection: DaySelection = "1-4".parse().unwrap();
+        selection.slow_only = true;
+        let days: Vec<Day> = selection.resolve_with(&timings).into_iter().collect();
+        assert_eq!(days, vec![Day(3)]);
+    }
+
+    #[test]
+    fn limits_day_selections_to_calendar() {
+        let mut selection: DaySelection = "10-15".parse().unwrap();
+        selection.year = Year::new(2025).unwrap();
+        let mut days: Vec<Day> = selection
+            .resolve_with(&Timings::default())
+            .into_iter()
+            .collect();
+        days.sort();
+        assert_eq!(days, vec![Day(10), Day(11), Day(12)]);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,254 @@
+/// Module that keeps a history of benchmark runs, so that changes of run times can be followed over time.
+/// Every stored run of `cargo time` appends the total run time of the days it measured.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::Timings;
+use crate::template::{Day, Year};
+
+/// Number of runs that are kept, older runs are dropped.
+const MAX_RUNS: usize = 100;
+
+/// Path of the history file of a benchmark table, e.g. `data/timings.history.json` for the default table.
+#[must_use]
+pub fn get_path(year: Year, table: Option<&str>) -> PathBuf {
+    match table {
+        Some(name) => year.data_dir().join(format!("timings.{name}.history.json")),
+        None => year.data_dir().join("timings.history.json"),
+    }
+}
+
+/// Total run times of the days that were measured in a single run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub totals: BTreeMap<Day, f64>,
+}
+
+/// Represents the benchmark runs of a table, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    /// Dehydrate the history to a JSON file.
+    pub fn store_file(&self, year: Year, table: Option<&str>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let path = get_path(year, table);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from a JSON file. If not present, returns an empty history.
+    /// Files that can not be read result in an error, so that they are not overwritten.
+    pub fn read_from_file(year: Year, table: Option<&str>) -> Result<Self, Error> {
+        let path = get_path(year, table);
+
+        if !path.exists() {
+            return Ok(History::default());
+        }
+
+        let s = fs::read_to_string(&path)?;
+        History::try_from(s).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Could not read benchmark history from `{}`: {e}",
+                    path.display()
+                ),
+            )
+        })
+    }
+
+    /// Append the timings of a run, dropping the oldest runs once `MAX_RUNS` is exceeded.
+    pub fn record(&mut self, timestamp: u64, timings: &Timings) {
+        let totals: BTreeMap<Day, f64> = timings
+            .data
+            .iter()
+            .filter(|t| !t.timed_out)
+            .map(|t| (t.day, t.total_nanos))
+            .collect();
+
+        if totals.is_empty() {
+            return;
+        }
+
+        self.runs.push(Run { timestamp, totals });
+
+        if self.runs.len() > MAX_RUNS {
+            self.runs.drain(..self.runs.len() - MAX_RUNS);
+        }
+    }
+
+    /// Total run times of a day in the order they were measured.
+    pub fn series(&self, day: Day) -> Vec<f64> {
+        self.runs
+            .iter()
+            .filter_map(|run| run.totals.get(&day).copied())
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<History> for JsonValue {
+    fn from(value: History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "runs".into(),
+            JsonValue::Array(value.runs.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_runs = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("runs")
+            .ok_or("expected JSON document to have key `runs`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.runs` to be an array.")?;
+
+        Ok(History {
+            runs: json_runs
+                .iter()
+                .map(Run::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Run> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "totals".into(),
+            JsonValue::Object(
+                value
+                    .totals
+                    .iter()
+                    .map(|(day, nanos)| (day.to_string(), JsonValue::Number(*nanos)))
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|t| *t as u64)
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let totals = json
+            .get("totals")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected run.totals to be an object.")?
+            .iter()
+            .map(|(day, nanos)| {
+                let day = Day::from_str(day).map_err(|_| "Expected run.totals keys to be days.")?;
+                let nanos = nanos
+                    .get::<f64>()
+                    .ok_or("Expected run.totals values to be numbers.")?;
+                Ok((day, *nanos))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Run { timestamp, totals })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{History, MAX_RUNS};
+    use crate::day;
+    use crate::template::timings::{Timing, TimingStats, Timings};
+    use tinyjson::JsonValue;
+
+    fn get_mock_timings(nanos: f64) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(3),
+                part_1_nanos: Some(1e6),
+                part_2_nanos: None,
+                total_nanos: nanos,
+                timed_out: false,
+                stats: TimingStats::default(),
+                meta: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn records_runs() {
+        let mut history = History::default();
+        history.record(1, &get_mock_timings(1_000.0));
+        history.record(2, &Timings::default());
+        history.record(3, &get_mock_timings(2_000.0));
+
+        assert_eq!(history.runs.len(), 2);
+        assert_eq!(history.series(day!(3)), vec![1_000.0, 2_000.0]);
+        assert!(history.series(day!(4)).is_empty());
+    }
+
+    #[test]
+    fn drops_oldest_runs() {
+        let mut history = History::default();
+        for i in 0..MAX_RUNS as u64 + 5 {
+            history.record(i, &get_mock_timings(1.0));
+        }
+
+        assert_eq!(history.runs.len(), MAX_RUNS);
+        assert_eq!(history.runs[0].timestamp, 5);
+    }
+
+    #[test]
+    fn serializes_history() {
+        let mut history = History::default();
+        history.record(1_700_000_000, &get_mock_timings(1_500.0));
+
+        let json = JsonValue::from(history.clone()).stringify().unwrap();
+        let parsed = History::try_from(json).unwrap();
+        assert_eq!(parsed.runs, history.runs);
+    }
+}
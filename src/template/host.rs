@@ -0,0 +1,71 @@
+/// Module that describes the machine solutions are run on.
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+/// Hardware and operating system the timings were measured on.
+pub struct Host {
+    pub os: String,
+    pub cpu: String,
+    pub cores: usize,
+}
+
+impl Host {
+    /// Detect the current host. Values that can not be determined are reported as `unknown`.
+    pub fn detect() -> Self {
+        Host {
+            os: detect_os(),
+            cpu: cpu().unwrap_or_else(|| "unknown".into()),
+            cores: std::thread::available_parallelism().map_or(1, std::num::NonZero::get),
+        }
+    }
+}
+
+fn detect_os() -> String {
+    let release = fs::read_to_string("/etc/os-release").ok().and_then(|s| {
+        s.lines()
+            .find_map(|l| l.strip_prefix("PRETTY_NAME="))
+            .map(|name| name.trim_matches('"').to_string())
+    });
+
+    let release = release.or_else(|| {
+        command_output("sw_vers", &["-productVersion"]).map(|version| format!("macOS {version}"))
+    });
+
+    format!(
+        "{} ({})",
+        release.unwrap_or_else(|| env::consts::OS.into()),
+        env::consts::ARCH
+    )
+}
+
+/// Model name of the CPU, e.g. `Apple M1` or `AMD Ryzen 7 5800X 8-Core Processor`.
+pub fn cpu() -> Option<String> {
+    fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|s| {
+            s.lines()
+                .find(|l| l.starts_with("model name"))
+                .and_then(|l| l.split_once(':'))
+                .map(|(_, name)| name.trim().to_string())
+        })
+        .or_else(|| command_output("sysctl", &["-n", "machdep.cpu.brand_string"]))
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let s = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !s.is_empty()).then_some(s)
+}
+
+/// Version of the rust compiler, e.g. `rustc 1.83.0 (90b35a623 2024-11-26)`.
+pub fn rustc_version() -> Option<String> {
+    command_output("rustc", &["--version"])
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
@@ -0,0 +1,481 @@
+/// Module that requests pages of the advent of code site politely: requests are spaced out by a minimum interval,
+/// responses are cached on disk and puzzles are never requested before they unlock.
+/// `https` URLs are fetched with `curl`, which has to be installed, plain `http` URLs, e.g. of a local mirror, over
+/// a `TcpStream`.
+use std::fmt::Display;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs, thread};
+
+use crate::template::config::Config;
+use crate::template::PuzzleId;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the requests of this template, as the advent of code site asks automated tools to do.
+/// Set `site.user_agent` in `aoc.toml` or `AOC_USER_AGENT` to add your contact details, e.g. your email address.
+pub const DEFAULT_USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+/// Minimum time between two requests to the site, also across runs of the CLI.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Debug)]
+pub enum HttpError {
+    /// The puzzle unlocks at the contained time.
+    NotUnlocked(SystemTime),
+    MissingSession,
+    CurlNotFound,
+    Status(u16),
+    BadResponse(String),
+    Io(io::Error),
+}
+
+impl Display for HttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpError::NotUnlocked(time) => write!(
+                f,
+                "the puzzle is not unlocked yet, it unlocks in {}.",
+                format_remaining(time.duration_since(SystemTime::now()).unwrap_or_default())
+            ),
+            HttpError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `ADVENT_OF_CODE_SESSION` or store it in the session file, `~/.adventofcode.session` by default."
+            ),
+            HttpError::CurlNotFound => write!(
+                f,
+                "curl is not present in environment, it is required to download from the site. Install it from https://curl.se."
+            ),
+            HttpError::Status(status) => write!(f, "the site responded with status {status}."),
+            HttpError::BadResponse(e) => write!(f, "could not read the response: {e}"),
+            HttpError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for HttpError {
+    fn from(e: io::Error) -> Self {
+        HttpError::Io(e)
+    }
+}
+
+/// A client for the advent of code site.
+pub struct Client {
+    pub base_url: String,
+    pub user_agent: String,
+    pub session: Option<String>,
+    pub min_interval: Duration,
+    /// Directory of cached responses and of the time of the last request.
+    pub cache_dir: PathBuf,
+    /// Source of the current time, replaceable in tests.
+    pub now: fn() -> SystemTime,
+}
+
+impl Client {
+    /// A client for the advent of code site. The session cookie is read from `ADVENT_OF_CODE_SESSION` or the
+    /// session file of the config, `~/.adventofcode.session` by default, like aoc-cli does.
+    #[must_use]
+    pub fn new(config: &Config) -> Self {
+        let session = env::var("ADVENT_OF_CODE_SESSION").ok().or_else(|| {
+            config
+                .session_file
+                .as_ref()
+                .and_then(|path| fs::read_to_string(path).ok())
+        });
+
+        Client {
+            base_url: BASE_URL.into(),
+            user_agent: config.user_agent.as_ref().map_or_else(
+                || DEFAULT_USER_AGENT.into(),
+                |contact| format!("{DEFAULT_USER_AGENT} by {contact}"),
+            ),
+            session: session
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            min_interval: MIN_INTERVAL,
+            cache_dir: PathBuf::from("target").join("http_cache"),
+            now: SystemTime::now,
+        }
+    }
+
+    /// Fetch the input of a puzzle. Inputs do not change, so a cached input is used unless `refresh` is set.
+    pub fn fetch_input(&self, puzzle: PuzzleId, refresh: bool) -> Result<String, HttpError> {
+        let unlock_time = puzzle.unlock_time();
+        if (self.now)() < unlock_time {
+            return Err(HttpError::NotUnlocked(unlock_time));
+        }
+
+        let path = format!("/{}/day/{}/input", puzzle.year, puzzle.day.into_inner());
+        let max_age = if refresh { Some(Duration::ZERO) } else { None };
+        self.get(&path, max_age)
+    }
+
+    /// Fetch a path of the site, e.g. `/2023/day/1/input`. A cached response is used if it is younger than
+    /// `max_age`, or regardless of its age without a `max_age`.
+    pub fn get(&self, path: &str, max_age: Option<Duration>) -> Result<String, HttpError> {
+        let cache_path = self.cache_dir.join(cache_key(path));
+
+        if let Some((fetched_at, body)) = read_cache(&cache_path) {
+            let age = (self.now)().duration_since(fetched_at).unwrap_or_default();
+            if max_age.is_none_or(|max_age| age < max_age) {
+                return Ok(body);
+            }
+        }
+
+        let session = self.session.as_deref().ok_or(HttpError::MissingSession)?;
+        let url = format!("{}{path}", self.base_url);
+        if !url.starts_with("http://") {
+            check_curl()?;
+        }
+        self.wait_for_turn()?;
+
+        let (status, body) = match url.strip_prefix("http://") {
+            Some(rest) => get_plain(rest, &self.user_agent, session)?,
+            None => get_curl(&url, &self.user_agent, session)?,
+        };
+
+        if status != 200 {
+            return Err(HttpError::Status(status));
+        }
+
+        fs::create_dir_all(&self.cache_dir)?;
+        let secs = (self.now)()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        fs::write(&cache_path, format!("{secs}\n{body}"))?;
+
+        Ok(body)
+    }
+
+    /// Sleep until `min_interval` passed since the last request, which is stored in the cache directory.
+    pub(crate) fn wait_for_turn(&self) -> Result<(), HttpError> {
+        let path = self.cache_dir.join("last_request");
+
+        let last = fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+
+        if let Some(last) = last {
+            let elapsed = SystemTime::now().duration_since(last).unwrap_or_default();
+            if let Some(remaining) = self.min_interval.checked_sub(elapsed) {
+                thread::sleep(remaining);
+            }
+        }
+
+        fs::create_dir_all(&self.cache_dir)?;
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis());
+        fs::write(&path, millis.to_string())?;
+        Ok(())
+    }
+}
+
+/// Format the time until an unlock, e.g. `2d 03h 04m 05s`.
+#[must_use]
+pub fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, hours, minutes, secs) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours:02}h {minutes:02}m {secs:02}s")
+    } else {
+        format!("{hours:02}h {minutes:02}m {secs:02}s")
+    }
+}
+
+/// File name of the cached response of a path, e.g. `2023_day_1_input` for `/2023/day/1/input`.
+fn cache_key(path: &str) -> String {
+    path.trim_matches('/')
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// A cached response: the first line holds the unix time at which it was fetched, the rest the body.
+fn read_cache(path: &Path) -> Option<(SystemTime, String)> {
+    let cached = fs::read_to_string(path).ok()?;
+    let (secs, body) = cached.split_once('\n')?;
+    let fetched_at = UNIX_EPOCH + Duration::from_secs(secs.parse().ok()?);
+    Some((fetched_at, body.to_string()))
+}
+
+/// `GET` a plain `http` URL, given without its scheme, e.g. `127.0.0.1:8080/2023/day/1/input`.
+fn get_plain(url: &str, user_agent: &str, session: &str) -> Result<(u16, String), HttpError> {
+    let (host, path) = url.split_at(url.find('/').unwrap_or(url.len()));
+    let path = if path.is_empty() { "/" } else { path };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{host}:80")
+    };
+
+    let mut stream = TcpStream::connect(address)?;
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+    write!(
+        stream,
+        "GET {path} HTTP/1.1\r\nHost: {host}\r\nUser-Agent: {user_agent}\r\nCookie: session={session}\r\nConnection: close\r\n\r\n"
+    )?;
+
+    let mut response = vec![];
+    stream.read_to_end(&mut response)?;
+    parse_response(&response)
+}
+
+/// Parse a raw response. The body is only converted to a string once it is decoded, since chunk sizes count bytes.
+fn parse_response(response: &[u8]) -> Result<(u16, String), HttpError> {
+    let end = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| HttpError::BadResponse("missing end of headers.".into()))?;
+    let head = String::from_utf8_lossy(&response[..end]);
+    let body = &response[end + 4..];
+
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| HttpError::BadResponse("malformed status line.".into()))?;
+
+    let is_chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding") && value.trim() == "chunked"
+        })
+    });
+
+    let body = if is_chunked {
+        decode_chunked(body)?
+    } else {
+        body.to_vec()
+    };
+    Ok((status, String::from_utf8_lossy(&body).into_owned()))
+}
+
+fn decode_chunked(mut body: &[u8]) -> Result<Vec<u8>, HttpError> {
+    let mut decoded = vec![];
+
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or_else(|| HttpError::BadResponse("malformed chunk.".into()))?;
+        let size = String::from_utf8_lossy(&body[..line_end]);
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or_default().trim(), 16)
+            .map_err(|e| HttpError::BadResponse(e.to_string()))?;
+
+        if size == 0 {
+            return Ok(decoded);
+        }
+
+        let rest = &body[line_end + 2..];
+        let chunk = rest
+            .get(..size)
+            .ok_or_else(|| HttpError::BadResponse("truncated chunk.".into()))?;
+        decoded.extend_from_slice(chunk);
+        body = rest[size..].strip_prefix(b"\r\n").unwrap_or(&rest[size..]);
+    }
+}
+
+/// Check that curl, which sends the `https` requests, is installed.
+pub fn check_curl() -> Result<(), HttpError> {
+    Command::new("curl")
+        .arg("--version")
+        .output()
+        .map_err(|_| HttpError::CurlNotFound)?;
+    Ok(())
+}
+
+/// `GET` an `https` URL with curl. The session cookie is passed on stdin, so that it does not show up in the
+/// process list.
+fn get_curl(url: &str, user_agent: &str, session: &str) -> Result<(u16, String), HttpError> {
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--location"])
+        .args(["--user-agent", user_agent])
+        .args(["--header", "@-"])
+        .args(["--write-out", "\n%{http_code}"])
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| HttpError::CurlNotFound)?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "Cookie: session={session}")?;
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(HttpError::BadResponse(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or_else(|| HttpError::BadResponse("missing status code.".into()))?;
+    let status = status
+        .trim()
+        .parse()
+        .map_err(|_| HttpError::BadResponse("malformed status code.".into()))?;
+
+    Ok((status, body.to_string()))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+    use std::{env, fs, process, thread};
+
+    use super::{cache_key, decode_chunked, format_remaining, get_curl, Client, HttpError};
+    use crate::day;
+    use crate::template::{PuzzleId, Year};
+
+    /// Serve canned responses to `count` connections on a local port. Sends each request head on the channel.
+    fn fake_server(responses: Vec<&'static str>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                tx.send(request).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (format!("http://{address}"), rx)
+    }
+
+    fn client(base_url: String, name: &str) -> Client {
+        let cache_dir = env::temp_dir().join(format!("aoc_http_test_{}_{name}", process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+
+        Client {
+            base_url,
+            user_agent: "test agent".into(),
+            session: Some("abc".into()),
+            min_interval: Duration::ZERO,
+            cache_dir,
+            now: SystemTime::now,
+        }
+    }
+
+    const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\n1 2\n";
+
+    #[test]
+    fn fetches_and_caches_inputs() {
+        let (url, requests) = fake_server(vec![OK]);
+        let client = client(url, "cache");
+        let puzzle = PuzzleId::new(Year::new(2023).unwrap(), day!(1));
+
+        assert_eq!(client.fetch_input(puzzle, false).unwrap(), "1 2\n");
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("User-Agent: test agent\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+
+        // the server only answers once, the second response has to come from the cache.
+        assert_eq!(client.fetch_input(puzzle, false).unwrap(), "1 2\n");
+        assert!(client
+            .cache_dir
+            .join(cache_key("/2023/day/1/input"))
+            .exists());
+    }
+
+    #[test]
+    fn spaces_out_requests() {
+        let (url, _requests) = fake_server(vec![OK, OK]);
+        let mut client = client(url, "interval");
+        client.min_interval = Duration::from_millis(300);
+
+        let started = Instant::now();
+        client.get("/a", None).unwrap();
+        client.get("/b", None).unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn reports_errors() {
+        let (url, _requests) = fake_server(vec!["HTTP/1.1 404 Not Found\r\n\r\n"]);
+        let client = client(url, "status");
+        assert!(matches!(
+            client.get("/a", None),
+            Err(HttpError::Status(404))
+        ));
+
+        let mut client = self::client("http://127.0.0.1:1".into(), "session");
+        client.session = None;
+        assert!(matches!(
+            client.get("/a", None),
+            Err(HttpError::MissingSession)
+        ));
+    }
+
+    #[test]
+    fn refuses_locked_puzzles() {
+        // no server is listening, a request would fail with an io error.
+        let mut client = client("http://127.0.0.1:1".into(), "locked");
+        client.now = || UNIX_EPOCH + Duration::from_secs(1_701_406_799);
+        let puzzle = PuzzleId::new(Year::new(2023).unwrap(), day!(1));
+
+        assert!(matches!(
+            client.fetch_input(puzzle, false),
+            Err(HttpError::NotUnlocked(_))
+        ));
+    }
+
+    #[test]
+    fn fetches_with_curl() {
+        // `https` URLs are sent with curl, which speaks plain `http` to the fake server just as well.
+        let (url, requests) = fake_server(vec![OK]);
+
+        let (status, body) =
+            get_curl(&format!("{url}/2023/day/1/input"), "test agent", "abc").unwrap();
+        assert_eq!((status, body.as_str()), (200, "1 2\n"));
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("User-Agent: test agent\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+    }
+
+    #[test]
+    fn formats_and_decodes() {
+        assert_eq!(
+            decode_chunked(b"4\r\n1 2\n\r\n3\r\n3 4\r\n0\r\n\r\n").unwrap(),
+            b"1 2\n3 4"
+        );
+        // chunk sizes count bytes, a character may be split across chunks.
+        assert_eq!(
+            decode_chunked(b"1\r\n\xe2\r\n2\r\n\xad\x90\r\n0\r\n\r\n").unwrap(),
+            "⭐".as_bytes()
+        );
+        assert_eq!(cache_key("/2023/day/1/input"), "2023_day_1_input");
+        assert_eq!(format_remaining(Duration::from_secs(3_723)), "01h 02m 03s");
+        assert_eq!(
+            format_remaining(Duration::from_secs(2 * 86_400 + 3_723)),
+            "2d 01h 02m 03s"
+        );
+    }
+}
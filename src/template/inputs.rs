@@ -0,0 +1,211 @@
+/// Module that validates downloaded puzzle inputs and keeps their checksums in a manifest.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+static MANIFEST_FILE_NAME: &str = "inputs.json";
+
+/// Path of the input manifest of a year, e.g. `data/inputs.json` for the primary year.
+#[must_use]
+pub fn get_path(year: Year) -> PathBuf {
+    year.data_dir().join(MANIFEST_FILE_NAME)
+}
+
+/// Reasons why a downloaded input is not a puzzle input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InvalidInput {
+    Empty,
+    Html,
+    NotLoggedIn,
+    NotUnlocked,
+}
+
+impl Display for InvalidInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidInput::Empty => write!(f, "the input is empty."),
+            InvalidInput::Html => write!(f, "the input is an HTML page."),
+            InvalidInput::NotLoggedIn => {
+                write!(f, "the session cookie is missing or expired.")
+            }
+            InvalidInput::NotUnlocked => write!(f, "the puzzle is not unlocked yet."),
+        }
+    }
+}
+
+/// Check that a downloaded input is not an error page and normalize it to end with a single newline.
+pub fn validate(input: &str) -> Result<String, InvalidInput> {
+    let trimmed = input.trim_end_matches(['\n', '\r']);
+
+    if trimmed.trim().is_empty() {
+        return Err(InvalidInput::Empty);
+    }
+
+    let start = trimmed.trim_start().to_lowercase();
+    if start.starts_with("<!doctype") || start.starts_with("<html") {
+        return Err(InvalidInput::Html);
+    }
+    if trimmed.starts_with("Puzzle inputs differ by user") {
+        return Err(InvalidInput::NotLoggedIn);
+    }
+    if trimmed.starts_with("Please don't repeatedly request this endpoint before it unlocks") {
+        return Err(InvalidInput::NotUnlocked);
+    }
+
+    Ok(format!("{trimmed}\n"))
+}
+
+/// Checksum of an input, the 64-bit FNV-1a hash of its bytes in hex.
+#[must_use]
+pub fn checksum(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// Represents the checksums of the downloaded inputs of a year.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Manifest {
+    pub checksums: BTreeMap<Day, String>,
+}
+
+impl Manifest {
+    /// Dehydrate a manifest of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let path = get_path(year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the manifest of a year from a JSON file. If not present, returns an empty manifest.
+    /// Files that can not be read result in an error, so that they are not overwritten.
+    pub fn read_from_file(year: Year) -> Result<Self, Error> {
+        let path = get_path(year);
+
+        if !path.exists() {
+            return Ok(Manifest::default());
+        }
+
+        let s = fs::read_to_string(&path)?;
+        Manifest::try_from(s).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Could not read input checksums from `{}`: {e}",
+                    path.display()
+                ),
+            )
+        })
+    }
+
+    pub fn get(&self, day: Day) -> Option<&str> {
+        self.checksums.get(&day).map(String::as_str)
+    }
+
+    pub fn record(&mut self, day: Day, checksum: String) {
+        self.checksums.insert(day, checksum);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Manifest> for JsonValue {
+    fn from(value: Manifest) -> Self {
+        let checksums: HashMap<String, JsonValue> = value
+            .checksums
+            .into_iter()
+            .map(|(day, checksum)| (day.to_string(), JsonValue::String(checksum)))
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("checksums".into(), JsonValue::Object(checksums));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Manifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let checksums = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("checksums")
+            .ok_or("expected JSON document to have key `checksums`.")?
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected `json.checksums` to be an object.")?;
+
+        Ok(Manifest {
+            checksums: checksums
+                .iter()
+                .map(|(day, checksum)| {
+                    let day = Day::from_str(day).map_err(|_| format!("`{day}` is not a day."))?;
+                    let checksum = checksum
+                        .get::<String>()
+                        .ok_or("expected checksums to be strings.")?;
+                    Ok((day, checksum.clone()))
+                })
+                .collect::<Result<_, String>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{checksum, validate, InvalidInput, Manifest};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn validates_inputs() {
+        assert_eq!(validate("1\n2\n\n"), Ok("1\n2\n".into()));
+        assert_eq!(validate("1\r\n2"), Ok("1\r\n2\n".into()));
+        assert_eq!(validate("  \n"), Err(InvalidInput::Empty));
+        assert_eq!(
+            validate("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Err(InvalidInput::Html)
+        );
+        assert_eq!(
+            validate("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            Err(InvalidInput::NotLoggedIn)
+        );
+    }
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(checksum(""), "cbf29ce484222325");
+        assert_eq!(checksum("a"), "af63dc4c8601ec8c");
+        assert_ne!(checksum("1\n2\n"), checksum("1\n3\n"));
+    }
+
+    #[test]
+    fn serializes_manifest() {
+        let mut manifest = Manifest::default();
+        manifest.record(day!(1), checksum("1\n"));
+        manifest.record(day!(12), checksum("2\n"));
+
+        let json = JsonValue::from(manifest).stringify().unwrap();
+        let parsed = Manifest::try_from(json).unwrap();
+        assert_eq!(parsed.get(day!(1)), Some(checksum("1\n").as_str()));
+        assert_eq!(parsed.get(day!(12)), Some(checksum("2\n").as_str()));
+        assert_eq!(parsed.get(day!(2)), None);
+    }
+}
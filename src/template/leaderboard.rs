@@ -0,0 +1,349 @@
+/// Module that reads the JSON of a private leaderboard and ranks its members, overall and per day.
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+    time::{Duration, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::http::format_remaining;
+use crate::template::{Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// A member of a private leaderboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: String,
+    pub stars: u32,
+    pub local_score: u32,
+    /// Unix time of the last star, `0` without stars.
+    pub last_star_ts: u64,
+    /// Unix times at which the stars of each day were collected.
+    pub completions: BTreeMap<Day, [Option<u64>; 2]>,
+}
+
+/// A private leaderboard, e.g. of `https://adventofcode.com/2023/leaderboard/private/view/<id>.json`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: Year,
+    pub members: Vec<Member>,
+}
+
+/// Completion of one day by a member, in seconds since the puzzle unlocked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayResult<'a> {
+    pub member: &'a Member,
+    pub part_one: Option<u64>,
+    pub part_two: Option<u64>,
+}
+
+impl DayResult<'_> {
+    /// Time between the two stars of the day.
+    #[must_use]
+    pub fn delta(&self) -> Option<u64> {
+        Some(self.part_two?.saturating_sub(self.part_one?))
+    }
+}
+
+impl Leaderboard {
+    /// Members ordered by their local score, ties are broken by whoever got their last star first.
+    #[must_use]
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.iter().collect();
+        members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star_ts, m.id));
+        members
+    }
+
+    /// Members that completed at least part one of a day, ordered by the time they took for both parts, then
+    /// for part one.
+    #[must_use]
+    pub fn day_results(&self, day: Day) -> Vec<DayResult<'_>> {
+        let unlock = PuzzleId::new(self.year, day).unlock_time();
+        let unlock_secs = unlock.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+
+        let mut results: Vec<DayResult> = self
+            .members
+            .iter()
+            .filter_map(|member| {
+                let [one, two] = member.completions.get(&day)?;
+                Some(DayResult {
+                    member,
+                    part_one: Some(one.as_ref()?.saturating_sub(unlock_secs)),
+                    part_two: two.map(|ts| ts.saturating_sub(unlock_secs)),
+                })
+            })
+            .collect();
+
+        results.sort_by_key(|r| (r.part_two.unwrap_or(u64::MAX), r.part_one, r.member.id));
+        results
+    }
+
+    /// The last day that any member collected a star for.
+    #[must_use]
+    pub fn latest_day(&self) -> Option<Day> {
+        self.members
+            .iter()
+            .filter_map(|m| m.completions.keys().last().copied())
+            .max()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Format the overall ranking as a table, with a star for every day of the calendar.
+#[must_use]
+pub fn format_ranking(leaderboard: &Leaderboard) -> String {
+    let ranking = leaderboard.ranking();
+    let width = name_width(&ranking);
+
+    let mut lines = vec![
+        format!("{ANSI_BOLD}{} leaderboard{ANSI_RESET}", leaderboard.year),
+        "------".into(),
+        format!(
+            "{:>4}  {:<width$}  {:>5}  {:>5}  Days",
+            "Rank", "Member", "Score", "Stars"
+        ),
+    ];
+
+    for (rank, member) in (1..).zip(ranking) {
+        let days: String = leaderboard
+            .year
+            .days()
+            .map(|day| match member.completions.get(&day) {
+                Some([Some(_), Some(_)]) => '★',
+                Some([Some(_), None]) => '☆',
+                _ => '·',
+            })
+            .collect();
+
+        lines.push(format!(
+            "{rank:>4}  {:<width$}  {:>5}  {:>5}  {days}",
+            member.name, member.local_score, member.stars
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Format the results of a day as a table, with the time each part took since the puzzle unlocked.
+#[must_use]
+pub fn format_day(leaderboard: &Leaderboard, day: Day) -> String {
+    let results = leaderboard.day_results(day);
+    let members: Vec<&Member> = results.iter().map(|r| r.member).collect();
+    let width = name_width(&members);
+    let time = |secs: Option<u64>| {
+        secs.map_or_else(|| "-".into(), |s| format_remaining(Duration::from_secs(s)))
+    };
+
+    let mut lines = vec![
+        format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"),
+        "------".into(),
+        format!(
+            "{:>4}  {:<width$}  {:>15}  {:>15}  {:>15}",
+            "Rank", "Member", "Part 1", "Part 2", "Delta"
+        ),
+    ];
+
+    for (rank, result) in (1..).zip(&results) {
+        lines.push(format!(
+            "{rank:>4}  {:<width$}  {:>15}  {:>15}  {:>15}",
+            result.member.name,
+            time(result.part_one),
+            time(result.part_two),
+            time(result.delta())
+        ));
+    }
+
+    if results.is_empty() {
+        lines.push("Nobody has solved this day yet.".into());
+    }
+
+    lines.join("\n")
+}
+
+fn name_width(members: &[&Member]) -> usize {
+    members
+        .iter()
+        .map(|m| m.name.chars().count())
+        .max()
+        .unwrap_or_default()
+        .max("Member".len())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let year = json
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .and_then(|event| Year::from_str(event).ok())
+            .ok_or("expected `json.event` to be a year.")?;
+
+        let members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `json.members` to be an object.")?;
+
+        Ok(Leaderboard {
+            year,
+            members: members
+                .values()
+                .map(Member::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn get_number(json: &HashMap<String, JsonValue>, key: &str) -> Option<u64> {
+    json.get(key)
+        .and_then(|v| v.get::<f64>())
+        .filter(|n| **n >= 0.0)
+        .map(|n| *n as u64)
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        let id = get_number(json, "id").ok_or("Expected member.id to be a number.")?;
+
+        // members without a display name are shown as anonymous on the site.
+        let name = json
+            .get("name")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .unwrap_or_else(|| format!("(anonymous user #{id})"));
+
+        let completions = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected member.completion_day_level to be an object.")?
+            .iter()
+            .map(|(day, parts)| {
+                let day = Day::from_str(day).map_err(|_| format!("`{day}` is not a day."))?;
+                let parts = parts
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected the completions of a day to be an object.")?;
+                let star = |part: &str| {
+                    parts
+                        .get(part)
+                        .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                        .and_then(|star| get_number(star, "get_star_ts"))
+                };
+                Ok((day, [star("1"), star("2")]))
+            })
+            .collect::<Result<_, String>>()?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(Member {
+            id,
+            name,
+            stars: get_number(json, "stars").unwrap_or_default() as u32,
+            local_score: get_number(json, "local_score").unwrap_or_default() as u32,
+            last_star_ts: get_number(json, "last_star_ts").unwrap_or_default(),
+            completions,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_day, format_ranking, Leaderboard};
+    use crate::day;
+
+    // day 1 of 2023 unlocked at 1701406800.
+    const JSON: &str = r#"{
+        "owner_id": 1,
+        "event": "2023",
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 5, "global_score": 0,
+                "last_star_ts": 1701493500,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1701407100, "star_index": 1}, "2": {"get_star_ts": 1701407400, "star_index": 2}},
+                    "2": {"1": {"get_star_ts": 1701493500, "star_index": 3}}
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 5, "global_score": 0,
+                "last_star_ts": 1701407000,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1701406900, "star_index": 4}, "2": {"get_star_ts": 1701407000, "star_index": 5}}
+                }
+            },
+            "3": {
+                "id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": 0, "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    fn leaderboard() -> Leaderboard {
+        Leaderboard::try_from(JSON.to_string()).unwrap()
+    }
+
+    #[test]
+    fn parses_leaderboards() {
+        let leaderboard = leaderboard();
+        assert_eq!(leaderboard.year.into_inner(), 2023);
+        assert_eq!(leaderboard.members.len(), 3);
+        assert_eq!(leaderboard.latest_day(), Some(day!(2)));
+
+        let anonymous = leaderboard.members.iter().find(|m| m.id == 2).unwrap();
+        assert_eq!(anonymous.name, "(anonymous user #2)");
+        assert_eq!(
+            anonymous.completions.get(&day!(1)),
+            Some(&[Some(1_701_406_900), Some(1_701_407_000)])
+        );
+
+        assert!(Leaderboard::try_from(r#"{"event": "2023"}"#.to_string()).is_err());
+    }
+
+    #[test]
+    fn ranks_members() {
+        let leaderboard = leaderboard();
+        let ranking: Vec<u64> = leaderboard.ranking().iter().map(|m| m.id).collect();
+        // equal scores are ranked by the time of the last star.
+        assert_eq!(ranking, vec![2, 1, 3]);
+
+        let results = leaderboard.day_results(day!(1));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].member.id, 2);
+        assert_eq!(results[0].part_one, Some(100));
+        assert_eq!(results[0].delta(), Some(100));
+        assert_eq!(results[1].part_two, Some(600));
+        assert_eq!(results[1].delta(), Some(300));
+
+        let results = leaderboard.day_results(day!(2));
+        assert_eq!(results[0].part_two, None);
+        assert_eq!(results[0].delta(), None);
+    }
+
+    #[test]
+    fn formats_tables() {
+        let leaderboard = leaderboard();
+
+        let ranking = format_ranking(&leaderboard);
+        assert!(ranking.contains("   1  (anonymous user #2)      5      2  ★·······"));
+        assert!(ranking.contains("   2  alice                    5      3  ★☆······"));
+
+        let day = format_day(&leaderboard, day!(2));
+        assert!(day.contains("   1  alice       00h 05m 00s                -                -"));
+        assert!(format_day(&leaderboard, day!(3)).contains("Nobody has solved this day yet."));
+    }
+}
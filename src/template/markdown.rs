@@ -0,0 +1,414 @@
+/// Module that renders the puzzle descriptions aoc-cli stores as markdown, e.g. `data/puzzles/01.md`, in the terminal.
+/// Only the subset of markdown that puzzle descriptions use is supported: headings, paragraphs, lists, code blocks,
+/// emphasis, inline code and links.
+use std::env;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    List(Vec<String>),
+    Code(Vec<String>),
+}
+
+/// A run of text with the same style.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Span {
+    text: String,
+    bold: bool,
+    italic: bool,
+}
+
+/// Render a puzzle description, wrapped to `width` columns. With a part, only that part is rendered.
+/// Returns `None` if the description does not contain the part yet, e.g. part two before part one is solved.
+#[must_use]
+pub fn render(markdown: &str, part: Option<u8>, width: usize) -> Option<String> {
+    let blocks = select_part(parse_blocks(markdown), part)?;
+    let width = width.max(20);
+    let mut out = String::new();
+
+    for block in blocks {
+        match block {
+            Block::Heading(text) => {
+                let spans = parse_inline(&text)
+                    .into_iter()
+                    .map(|span| Span { bold: true, ..span })
+                    .collect::<Vec<_>>();
+                out.push_str(&wrap(&spans, width, "", ""));
+            }
+            Block::Paragraph(text) => out.push_str(&wrap(&parse_inline(&text), width, "", "")),
+            Block::List(items) => {
+                for item in items {
+                    out.push_str(&wrap(&parse_inline(&item), width, "  • ", "    "));
+                }
+            }
+            Block::Code(lines) => {
+                for line in lines {
+                    out.push_str("    ");
+                    out.push_str(&line);
+                    out.push('\n');
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    Some(out)
+}
+
+/// Width of the terminal that stdout is connected to, falling back to `$COLUMNS` and then to 80 columns.
+#[must_use]
+pub fn terminal_width() -> usize {
+    #[cfg(unix)]
+    {
+        // SAFETY: `winsize` is a plain C struct, for which all zeroes is a valid value.
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        // SAFETY: `TIOCGWINSZ` only writes to the `winsize` that is passed to it.
+        let res = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+        if res == 0 && size.ws_col > 0 {
+            return usize::from(size.ws_col);
+        }
+    }
+
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .filter(|columns| *columns > 0)
+        .unwrap_or(80)
+}
+
+/// Keep the blocks of one part. Part two starts at the `--- Part Two ---` heading.
+fn select_part(blocks: Vec<Block>, part: Option<u8>) -> Option<Vec<Block>> {
+    let part_two = blocks
+        .iter()
+        .position(|block| matches!(block, Block::Heading(text) if text.contains("Part Two")));
+
+    match (part, part_two) {
+        (Some(1), Some(index)) => Some(blocks.into_iter().take(index).collect()),
+        (Some(2), Some(index)) => Some(blocks.into_iter().skip(index).collect()),
+        (Some(2), None) => None,
+        _ => Some(blocks),
+    }
+}
+
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut list: Vec<String> = vec![];
+    let mut lines = markdown.lines();
+
+    let flush = |blocks: &mut Vec<Block>, paragraph: &mut Vec<&str>, list: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph.join(" ")));
+            paragraph.clear();
+        }
+        if !list.is_empty() {
+            blocks.push(Block::List(std::mem::take(list)));
+        }
+    };
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            flush(&mut blocks, &mut paragraph, &mut list);
+            let code = lines
+                .by_ref()
+                .take_while(|line| !line.trim_start().starts_with("```"))
+                .map(ToString::to_string)
+                .collect();
+            blocks.push(Block::Code(code));
+        } else if trimmed.is_empty() {
+            flush(&mut blocks, &mut paragraph, &mut list);
+        } else if is_underline(trimmed) {
+            // setext headings are underlined, a line of dashes on its own is a horizontal rule.
+            if !paragraph.is_empty() {
+                blocks.push(Block::Heading(paragraph.join(" ")));
+                paragraph.clear();
+            }
+        } else if let Some(heading) = trimmed.strip_prefix('#') {
+            flush(&mut blocks, &mut paragraph, &mut list);
+            blocks.push(Block::Heading(heading.trim_matches('#').trim().to_string()));
+        } else if let Some(item) = ["* ", "- ", "+ "]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker))
+        {
+            if !paragraph.is_empty() {
+                flush(&mut blocks, &mut paragraph, &mut list);
+            }
+            list.push(item.trim().to_string());
+        } else if let (true, Some(item)) = (line.starts_with(' '), list.last_mut()) {
+            item.push(' ');
+            item.push_str(trimmed);
+        } else if line.starts_with("    ") && paragraph.is_empty() {
+            flush(&mut blocks, &mut paragraph, &mut list);
+            match blocks.last_mut() {
+                Some(Block::Code(code)) => code.push(line[4..].to_string()),
+                _ => blocks.push(Block::Code(vec![line[4..].to_string()])),
+            }
+        } else {
+            if !list.is_empty() {
+                flush(&mut blocks, &mut paragraph, &mut list);
+            }
+            paragraph.push(trimmed);
+        }
+    }
+
+    flush(&mut blocks, &mut paragraph, &mut list);
+    blocks
+}
+
+fn is_underline(line: &str) -> bool {
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+/// Whether the `[` at the start of `chars` opens a link, i.e. its matching `]` is directly followed by `(`.
+fn is_link_start(chars: &[char]) -> bool {
+    let mut depth = 0;
+    for (i, c) in chars.iter().enumerate() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return chars.get(i + 1) == Some(&'(');
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Split inline markdown into styled spans. Emphasis is rendered in italics, strong emphasis and inline code in
+/// bold. Links are replaced by their text.
+fn parse_inline(text: &str) -> Vec<Span> {
+    let mut spans = vec![];
+    let mut current = Span {
+        text: String::new(),
+        bold: false,
+        italic: false,
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+
+    let push = |spans: &mut Vec<Span>, current: &mut Span| {
+        if !current.text.is_empty() {
+            spans.push(current.clone());
+            current.text.clear();
+        }
+    };
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                current.text.push(chars[i + 1]);
+                i += 1;
+            }
+            '`' => {
+                let end = chars[i + 1..].iter().position(|c| *c == '`');
+                if let Some(end) = end {
+                    push(&mut spans, &mut current);
+                    let code: String = chars[i + 1..i + 1 + end].iter().collect();
+                    // emphasized code, like the answers to the examples, is written as `*142*`.
+                    let emphasized = code.strip_prefix('*').and_then(|c| c.strip_suffix('*'));
+                    spans.push(Span {
+                        text: emphasized.unwrap_or(&code).to_string(),
+                        bold: true,
+                        italic: current.italic || emphasized.is_some(),
+                    });
+                    i += end + 1;
+                } else {
+                    current.text.push('`');
+                }
+            }
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                push(&mut spans, &mut current);
+                current.bold = !current.bold;
+                i += 1;
+            }
+            '*' => {
+                push(&mut spans, &mut current);
+                current.italic = !current.italic;
+            }
+            ']' if chars.get(i + 1) == Some(&'(') => {
+                // skip the target of a link.
+                if let Some(end) = chars[i..].iter().position(|c| *c == ')') {
+                    i += end;
+                }
+            }
+            '[' if is_link_start(&chars[i..]) => {}
+            c => current.text.push(c),
+        }
+        i += 1;
+    }
+
+    push(&mut spans, &mut current);
+    spans
+}
+
+/// Wrap spans to a width, prefixing the first line with `first_indent` and the others with `indent`.
+fn wrap(spans: &[Span], width: usize, first_indent: &str, indent: &str) -> String {
+    // words may consist of several spans, e.g. an emphasized number followed by a period.
+    let mut words: Vec<Vec<Span>> = vec![vec![]];
+    for span in spans {
+        for (i, part) in span.text.split(' ').enumerate() {
+            if i > 0 {
+                words.push(vec![]);
+            }
+            if !part.is_empty() {
+                words.last_mut().unwrap().push(Span {
+                    text: part.to_string(),
+                    ..span.clone()
+                });
+            }
+        }
+    }
+
+    let mut out = first_indent.to_string();
+    let mut column = first_indent.chars().count();
+    let mut is_line_start = true;
+
+    for word in words.iter().filter(|word| !word.is_empty()) {
+        let len: usize = word.iter().map(|span| span.text.chars().count()).sum();
+
+        if !is_line_start && column + 1 + len > width {
+            out.push('\n');
+            out.push_str(indent);
+            column = indent.chars().count();
+            is_line_start = true;
+        }
+        if !is_line_start {
+            out.push(' ');
+            column += 1;
+        }
+
+        for span in word {
+            let style = match (span.bold, span.italic) {
+                (true, true) => format!("{ANSI_BOLD}{ANSI_ITALIC}"),
+                (true, false) => ANSI_BOLD.to_string(),
+                (false, true) => ANSI_ITALIC.to_string(),
+                (false, false) => String::new(),
+            };
+            if style.is_empty() {
+                out.push_str(&span.text);
+            } else {
+                out.push_str(&format!("{style}{}{ANSI_RESET}", span.text));
+            }
+        }
+
+        column += len;
+        is_line_start = false;
+    }
+
+    out.push('\n');
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_blocks, parse_inline, render, wrap, Block, Span};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    const PUZZLE: &str = r"\--- Day 1: Trebuchet?! ---
+----------
+
+Something is wrong with global snow production, and you've been selected to take a look.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+Adding these together produces `*142*`.
+
+* one
+* two
+  continued
+
+\--- Part Two ---
+----------
+
+Your calculation isn't quite right. See [the example](/2023/day/1#part2).
+";
+
+    fn span(text: &str, bold: bool, italic: bool) -> Span {
+        Span {
+            text: text.into(),
+            bold,
+            italic,
+        }
+    }
+
+    #[test]
+    fn parses_blocks() {
+        let blocks = parse_blocks(PUZZLE);
+        assert_eq!(blocks.len(), 8);
+        assert_eq!(
+            blocks[0],
+            Block::Heading(r"\--- Day 1: Trebuchet?! ---".into())
+        );
+        assert_eq!(
+            blocks[3],
+            Block::Code(vec!["1abc2".into(), "pqr3stu8vwx".into()])
+        );
+        assert_eq!(
+            blocks[5],
+            Block::List(vec!["one".into(), "two continued".into()])
+        );
+        assert_eq!(blocks[6], Block::Heading(r"\--- Part Two ---".into()));
+    }
+
+    #[test]
+    fn parses_inline_styles() {
+        assert_eq!(
+            parse_inline(r"produces `*142*`, *not* **this** \*"),
+            vec![
+                span("produces ", false, false),
+                span("142", true, true),
+                span(", ", false, false),
+                span("not", false, true),
+                span(" ", false, false),
+                span("this", true, false),
+                span(" *", false, false),
+            ]
+        );
+        assert_eq!(
+            parse_inline("see [the example](/2023/day/1#part2)."),
+            vec![span("see the example.", false, false)]
+        );
+        assert_eq!(
+            parse_inline("in [1,2,3], see [the example](/2023/day/1)."),
+            vec![span("in [1,2,3], see the example.", false, false)]
+        );
+    }
+
+    #[test]
+    fn wraps_words() {
+        let spans = parse_inline("one two *three* four");
+        assert_eq!(
+            wrap(&spans, 9, "- ", "  "),
+            format!("- one two\n  {ANSI_ITALIC}three{ANSI_RESET}\n  four\n")
+        );
+    }
+
+    #[test]
+    fn renders_parts() {
+        let part_one = render(PUZZLE, Some(1), 80).unwrap();
+        assert!(part_one.starts_with(&format!("{ANSI_BOLD}---{ANSI_RESET}")));
+        assert!(part_one.contains("    pqr3stu8vwx\n"));
+        assert!(part_one.contains("  • two continued\n"));
+        assert!(!part_one.contains("Two"));
+
+        let part_two = render(PUZZLE, Some(2), 80).unwrap();
+        assert!(part_two.contains("Two"));
+        assert!(part_two.contains("See the example."));
+
+        assert!(render("Part one only.", Some(2), 80).is_none());
+        assert!(render("Part one only.", None, 80).is_some());
+    }
+}
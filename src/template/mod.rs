@@ -1,68 +1,185 @@
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
 pub mod runner;
 
 pub use day::*;
+pub use run_multi::Limits;
+pub use submission::SubmitOptions;
+pub use year::*;
 
+mod answers;
+mod charts;
+mod clock;
+mod criterion;
 mod day;
+mod history;
+mod host;
+mod http;
+mod inputs;
+mod leaderboard;
+mod markdown;
 mod readme_benchmarks;
+mod readme_stars;
+mod report;
 mod run_multi;
+mod stats;
+mod submission;
 mod timings;
+mod variants;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of the primary year to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
+    read_puzzle_file(folder, PuzzleId::primary(day))
+}
+
+/// Helper function that reads a text file of the primary year to string, appending a part suffix. E.g. like `01-2.txt`.
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    read_puzzle_file_part(folder, PuzzleId::primary(day), part)
+}
+
+/// Helper function that reads a text file of any puzzle to a string.
+#[must_use]
+pub fn read_puzzle_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Helper function that reads a text file of any puzzle to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_puzzle_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(puzzle.year.data_dir())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Reads the puzzle input for a solution binary.
+///
+/// By default, this reads the input file of the puzzle. Passing `--input <path>` to the binary reads another file
+/// instead, which allows running a solution against generated inputs.
+#[must_use]
+pub fn read_input(puzzle: PuzzleId) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    match args.iter().position(|x| x == "--input") {
+        Some(i) => {
+            let path = args.get(i + 1).expect("missing path after `--input`");
+            fs::read_to_string(path).expect("could not open input file")
+        }
+        None => read_puzzle_file("inputs", puzzle),
+    }
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Solutions for years other than the primary year of the workspace are declared with a leading year, e.g.
+/// `solution!(year = 2022, 5)`. The function `puzzle()` returns the full [`PuzzleId`] of the solution.
+///
+/// Alternative implementations of a part can be registered as named variants, e.g.
+/// `solution!(12, variants: [(1, "naive", part_one_naive)])`. A variant is selected by passing
+/// `--variant <name>` to the binary, `--list-variants` prints all registered variant names. Single-part days
+/// declare their part before the variants, e.g. `solution!(25, 1, variants: [(1, "naive", part_one_naive)])`.
+///
+/// A function that parses the input for both parts can be passed as a trailing `parse:` option, e.g.
+/// `solution!(5, parse: parse_input)`. Its run time is reported separately from the parts.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
-    };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
-    };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
-    };
-
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl [$($year:tt)*] [$($parse:expr)?] $day:expr, $( [$func:expr, $part:expr] )*; $( [$vfunc:expr, $vpart:expr, $vname:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The year of the puzzle, `None` for the primary year of the workspace.
+        const YEAR: Option<$crate::template::Year> = $($year)*;
+
+        /// The current puzzle.
+        #[allow(dead_code)]
+        fn puzzle() -> $crate::template::PuzzleId {
+            $crate::template::PuzzleId::new(YEAR.unwrap_or_else($crate::template::Year::primary), DAY)
+        }
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(not(feature = "dhat-heap"))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+
+            if std::env::args().any(|x| x == "--list-variants") {
+                print_variants(&[DEFAULT_VARIANT, $( $vname ),*]);
+                return;
+            }
+
+            configure_threads();
+
+            let input = $crate::template::read_input(puzzle());
+            let variant = selected_variant();
+
+            if variant == DEFAULT_VARIANT {
+                $( run_parse($parse, &input); )?
+                $( run_part($func, &input, puzzle(), $part); )*
+                return;
+            }
+
+            let mut found = false;
+            $(
+                if variant == $vname {
+                    run_part($vfunc, &input, puzzle(), $vpart);
+                    found = true;
+                }
+            )*
+
+            if !found {
+                eprintln!("Unknown variant `{variant}` for {}.", puzzle());
+                std::process::exit(1);
+            }
         }
     };
+
+    (@year [$($year:tt)*] $day:expr $(, parse: $parse:expr)?) => {
+        $crate::solution!(@impl [$($year)*] [$($parse)?] $day, [part_one, 1] [part_two, 2];);
+    };
+    (@year [$($year:tt)*] $day:expr, 1 $(, parse: $parse:expr)?) => {
+        $crate::solution!(@impl [$($year)*] [$($parse)?] $day, [part_one, 1];);
+    };
+    (@year [$($year:tt)*] $day:expr, 2 $(, parse: $parse:expr)?) => {
+        $crate::solution!(@impl [$($year)*] [$($parse)?] $day, [part_two, 2];);
+    };
+    (@year [$($year:tt)*] $day:expr, variants: [$( ($vpart:expr, $vname:literal, $vfunc:expr) ),* $(,)?] $(, parse: $parse:expr)?) => {
+        $crate::solution!(@impl [$($year)*] [$($parse)?] $day, [part_one, 1] [part_two, 2]; $( [$vfunc, $vpart, $vname] )*);
+    };
+    (@year [$($year:tt)*] $day:expr, 1, variants: [$( ($vpart:expr, $vname:literal, $vfunc:expr) ),* $(,)?] $(, parse: $parse:expr)?) => {
+        $crate::solution!(@impl [$($year)*] [$($parse)?] $day, [part_one, 1]; $( [$vfunc, $vpart, $vname] )*);
+    };
+    (@year [$($year:tt)*] $day:expr, 2, variants: [$( ($vpart:expr, $vname:literal, $vfunc:expr) ),* $(,)?] $(, parse: $parse:expr)?) => {
+        $crate::solution!(@impl [$($year)*] [$($parse)?] $day, [part_two, 2]; $( [$vfunc, $vpart, $vname] )*);
+    };
+
+    (year = $year:literal, $($rest:tt)*) => {
+        $crate::solution!(@year [Some($crate::year!($year))] $($rest)*);
+    };
+    ($($rest:tt)*) => {
+        $crate::solution!(@year [None] $($rest)*);
+    };
 }
@@ -1,9 +1,10 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, str::FromStr, time::Duration};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::alloc::AllocStats;
+use crate::template::timings::{PartStats, Timing, Timings};
+use crate::template::{Day, PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -13,6 +14,15 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -20,22 +30,204 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
+}
+
+/// A column of the benchmark table in addition to the run times of both parts.
+/// The columns `samples`, `median` and `stddev` are added for each part, the others once per day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Samples,
+    Median,
+    StdDev,
+    Allocated,
+    Parse,
+    PeakMemory,
+    Allocations,
+    CpuTime,
+    MaxRss,
+    Percent,
+}
+
+impl Column {
+    const ALL: [Column; 10] = [
+        Column::Samples,
+        Column::Median,
+        Column::StdDev,
+        Column::Allocated,
+        Column::Parse,
+        Column::PeakMemory,
+        Column::Allocations,
+        Column::CpuTime,
+        Column::MaxRss,
+        Column::Percent,
+    ];
+
+    /// Name of the column in the table options.
+    fn key(self) -> &'static str {
+        match self {
+            Column::Samples => "samples",
+            Column::Median => "median",
+            Column::StdDev => "stddev",
+            Column::Allocated => "allocated",
+            Column::Parse => "parse",
+            Column::PeakMemory => "memory",
+            Column::Allocations => "allocations",
+            Column::CpuTime => "cpu",
+            Column::MaxRss => "rss",
+            Column::Percent => "percent",
+        }
+    }
+
+    fn is_per_part(self) -> bool {
+        matches!(
+            self,
+            Column::Samples | Column::Median | Column::StdDev | Column::Allocated
+        )
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Column::Samples => "samples",
+            Column::Median => "median",
+            Column::StdDev => "stddev",
+            Column::Allocated => "allocated",
+            Column::Parse => "Parse",
+            Column::PeakMemory => "Peak memory",
+            Column::Allocations => "Allocations",
+            Column::CpuTime => "CPU time",
+            Column::MaxRss => "Max RSS",
+            Column::Percent => "% of total",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Column::ALL
+            .into_iter()
+            .find(|c| c.key() == s)
+            .ok_or_else(|| Error::Parser(format!("Unknown benchmark column `{s}`.")))
+    }
+}
+
+/// Order of the rows of the benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// The slowest day first.
+    Cost,
+}
+
+/// Layout of a benchmark table. The options are kept in a comment that directly follows the opening marker
+/// of the table, e.g. `<!--- options: columns=median,percent sort=cost highlight=3 --->`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableOptions {
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    /// Number of slowest days that are highlighted.
+    pub highlight: usize,
+}
+
+static OPTIONS_PREFIX: &str = "<!--- options:";
+
+impl TableOptions {
+    /// Read the options of an existing table. Tables without an options comment use the given defaults.
+    fn from_table(table: &str, defaults: &Self) -> Result<Self, Error> {
+        match table.lines().nth(1) {
+            Some(line) if line.starts_with(OPTIONS_PREFIX) => line.parse(),
+            _ => Ok(defaults.clone()),
+        }
+    }
+
+    fn to_comment(&self) -> Option<String> {
+        if *self == Self::default() {
+            return None;
+        }
+
+        let columns: Vec<&str> = self.columns.iter().map(|c| c.key()).collect();
+
+        let sort = match self.sort {
+            SortOrder::Day => "day",
+            SortOrder::Cost => "cost",
+        };
+
+        Some(format!(
+            "{OPTIONS_PREFIX} columns={} sort={sort} highlight={} --->",
+            columns.join(","),
+            self.highlight
+        ))
+    }
+}
+
+impl FromStr for TableOptions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = Self::default();
+
+        let body = s
+            .trim()
+            .strip_prefix(OPTIONS_PREFIX)
+            .and_then(|s| s.strip_suffix("--->"))
+            .ok_or_else(|| Error::Parser(format!("Invalid table options `{s}`.")))?;
+
+        for option in body.split_whitespace() {
+            let err = || Error::Parser(format!("Invalid table option `{option}`."));
+            let (key, value) = option.split_once('=').ok_or_else(err)?;
+
+            match key {
+                "columns" => {
+                    options.columns = value
+                        .split(',')
+                        .filter(|c| !c.is_empty())
+                        .map(str::parse)
+                        .collect::<Result<_, _>>()?;
+                }
+                "sort" => {
+                    options.sort = match value {
+                        "day" => SortOrder::Day,
+                        "cost" => SortOrder::Cost,
+                        _ => return Err(err()),
+                    }
+                }
+                "highlight" => options.highlight = value.parse().map_err(|_| err())?,
+                _ => return Err(err()),
+            }
+        }
+
+        Ok(options)
+    }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Every year and every named table has its own marker in the readme.
+/// The default table of the primary year uses the plain marker.
+fn get_marker(year: Year, table: Option<&str>) -> String {
+    let mut qualifiers: Vec<String> = vec![];
+    if !year.is_primary() {
+        qualifiers.push(year.to_string());
+    }
+    qualifiers.extend(table.map(Into::into));
+
+    if qualifiers.is_empty() {
+        MARKER.into()
+    } else {
+        format!("<!--- benchmarking table {} --->", qualifiers.join(" "))
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locate a table that is delimited by two occurrences of `marker`.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "Too many occurrences of marker `{marker}` in README."
+        )));
     }
 
     let pos_start = matches
@@ -51,76 +243,251 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{value:.1}{}", units[unit])
+    }
+}
+
+fn format_part_column(
+    column: Column,
+    stats: Option<&PartStats>,
+    alloc: Option<&AllocStats>,
+) -> String {
+    let value = match column {
+        Column::Samples => stats.map(|stats| stats.samples.to_string()),
+        Column::Median => stats.map(|stats| format_nanos(stats.median_nanos)),
+        Column::Allocated => alloc.map(|alloc| format_bytes(alloc.bytes)),
+        _ => stats.map(|stats| format_nanos(stats.stddev_nanos)),
+    };
+    format!("`{}`", value.unwrap_or_else(|| "-".into()))
+}
+
+fn format_day_column(column: Column, timing: &Timing, total_nanos: f64) -> String {
+    let stats = &timing.stats;
+    let value = match column {
+        Column::Parse => stats.parse_nanos.map(format_nanos),
+        Column::PeakMemory => stats.peak_memory_bytes.map(format_bytes),
+        Column::Allocations => stats.allocations.map(|x| x.to_string()),
+        Column::CpuTime => stats.usage.as_ref().map(|u| format_nanos(u.cpu_nanos())),
+        Column::MaxRss => stats.usage.as_ref().map(|u| format_bytes(u.max_rss_bytes)),
+        _ => {
+            (total_nanos > 0.0).then(|| format!("{:.1}%", timing.total_nanos / total_nanos * 100.0))
+        }
+    };
+    format!("`{}`", value.unwrap_or_else(|| "-".into()))
+}
+
+fn construct_table(
+    prefix: &str,
+    year: Year,
+    table: Option<&str>,
+    options: &TableOptions,
+    timings: Timings,
+    total_millis: f64,
+) -> String {
+    let marker = get_marker(year, table);
+    let mut header = if year.is_primary() {
+        format!("{prefix} Benchmarks")
+    } else {
+        format!("{prefix} {year} Benchmarks")
+    };
+    if let Some(name) = table {
+        header.push_str(&format!(" ({name})"));
+    }
+
+    let part_columns: Vec<Column> = options
+        .columns
+        .iter()
+        .copied()
+        .filter(|c| c.is_per_part())
+        .collect();
+    let day_columns: Vec<Column> = options
+        .columns
+        .iter()
+        .copied()
+        .filter(|c| !c.is_per_part())
+        .collect();
+
+    let mut titles: Vec<String> = vec!["Day".into()];
+    for part in 1..=2 {
+        titles.push(format!("Part {part}"));
+        titles.extend(
+            part_columns
+                .iter()
+                .map(|c| format!("Part {part} {}", c.title())),
+        );
+    }
+    titles.extend(day_columns.iter().map(|c| c.title().to_string()));
+
+    let mut lines: Vec<String> = vec![marker.clone()];
+    lines.extend(options.to_comment());
+    lines.extend([
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+        format!("| {} |", titles.join(" | ")),
+        format!("| {}  |", vec![":---:"; titles.len()].join(" | ")),
+    ]);
+
+    let mut rows = timings.data;
+    let total_nanos: f64 = rows.iter().map(|t| t.total_nanos).sum();
+
+    let mut by_cost: Vec<&Timing> = rows.iter().collect();
+    by_cost.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    let highlighted: Vec<Day> = by_cost
+        .iter()
+        .filter(|t| t.total_nanos > 0.0)
+        .take(options.highlight)
+        .map(|t| t.day)
+        .collect();
+
+    if options.sort == SortOrder::Cost {
+        rows.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    for timing in &rows {
+        let puzzle = PuzzleId::new(year, timing.day);
+        let missing = if timing.timed_out { "TIMEOUT" } else { "-" };
+        // the last day of a year only has one part, so there is nothing missing.
+        let missing_part_2 = if puzzle.part_count() < 2 {
+            "n/a"
+        } else {
+            missing
+        };
+
+        let mut day = format!("[Day {}]({})", timing.day.into_inner(), puzzle.bin_path());
+        if highlighted.contains(&timing.day) {
+            day = format!("**{day}**");
+        }
+
+        let mut cells = vec![day];
+        let parts = [
+            (
+                timing.part_1_nanos,
+                missing,
+                &timing.stats.part_1,
+                &timing.stats.part_1_alloc,
+            ),
+            (
+                timing.part_2_nanos,
+                missing_part_2,
+                &timing.stats.part_2,
+                &timing.stats.part_2_alloc,
+            ),
+        ];
+        for (nanos, missing, stats, alloc) in parts {
+            cells.push(format!(
+                "`{}`",
+                nanos.map_or_else(|| missing.into(), format_nanos)
+            ));
+            cells.extend(
+                part_columns
+                    .iter()
+                    .map(|c| format_part_column(*c, stats.as_ref(), alloc.as_ref())),
+            );
+        }
+        cells.extend(
+            day_columns
+                .iter()
+                .map(|c| format_day_column(*c, timing, total_nanos)),
+        );
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    table: Option<&str>,
+    timings: Timings,
+    total_millis: f64,
+    defaults: &TableOptions,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &get_marker(year, table))?;
+    let options = TableOptions::from_table(&s[positions.pos_start..positions.pos_end], defaults)?;
+    let table = construct_table("##", year, table, &options, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Update the benchmark table of a year in the readme. Named tables, e.g. for different machines, have their
+/// own marker like `<!--- benchmarking table laptop --->`. Tables without an options comment use `defaults`.
+pub fn update(
+    timings: Timings,
+    year: Year,
+    table: Option<&str>,
+    defaults: &TableOptions,
+) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, table, timings, total_millis, defaults)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, Column, SortOrder, TableOptions, MARKER};
+    use crate::{
+        day,
+        template::timings::{PartStats, Timing, TimingStats, Timings},
+        template::Year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1_nanos: Some(10e6),
+                    part_2_nanos: Some(20e6),
                     total_nanos: 3e+10,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1_nanos: Some(30e6),
+                    part_2_nanos: Some(40e6),
                     total_nanos: 7e+10,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1_nanos: Some(40e6),
+                    part_2_nanos: Some(50e6),
                     total_nanos: 9e+10,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 },
             ],
         }
@@ -130,36 +497,152 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn formats_timed_out_days() {
+        let mut timings = get_mock_timings();
+        timings.data.push(Timing {
+            day: day!(5),
+            part_1_nanos: None,
+            part_2_nanos: None,
+            total_nanos: 0_f64,
+            timed_out: true,
+            stats: TimingStats::default(),
+            meta: None,
+        });
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            Year::primary(),
+            None,
+            timings,
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+        assert!(s.contains("| [Day 5](./src/bin/05.rs) | `TIMEOUT` | `TIMEOUT` |"));
+    }
+
+    #[test]
+    fn formats_single_part_days() {
+        let mut timings = get_mock_timings();
+        timings.data.push(Timing {
+            day: day!(25),
+            part_1_nanos: Some(5e6),
+            part_2_nanos: None,
+            total_nanos: 5e+6,
+            timed_out: false,
+            stats: TimingStats::default(),
+            meta: None,
+        });
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            Year::primary(),
+            None,
+            timings,
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+        assert!(s.contains("| [Day 25](./src/bin/25.rs) | `5.0ms` | `n/a` |"));
+    }
+
+    #[test]
+    fn updates_tables_of_other_years() {
+        let marker = "<!--- benchmarking table 2015 --->";
+        let mut s = format!("{MARKER}{MARKER}\n{marker}{marker}");
+        update_content(
+            &mut s,
+            Year::new(2015).unwrap(),
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+        assert!(s.contains("## 2015 Benchmarks"));
+        assert!(s.contains("| [Day 1](./src/bin/2015-01.rs) | `10.0ms` | `20.0ms` |"));
+        assert!(s.starts_with(&format!("{MARKER}{MARKER}\n")));
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,9 +651,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -179,4 +662,113 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn parses_table_options() {
+        let options: TableOptions =
+            "<!--- options: columns=median,stddev,percent sort=cost highlight=3 --->"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            options.columns,
+            vec![Column::Median, Column::StdDev, Column::Percent]
+        );
+        assert_eq!(options.sort, SortOrder::Cost);
+        assert_eq!(options.highlight, 3);
+        assert_eq!(
+            options
+                .to_comment()
+                .unwrap()
+                .parse::<TableOptions>()
+                .unwrap(),
+            options
+        );
+
+        assert!("<!--- options: columns=foo --->"
+            .parse::<TableOptions>()
+            .is_err());
+        assert!("<!--- options: sort=random --->"
+            .parse::<TableOptions>()
+            .is_err());
+    }
+
+    #[test]
+    fn formats_configured_tables() {
+        let mut timings = get_mock_timings();
+        timings.data[2].stats.part_1 = Some(PartStats {
+            samples: 10,
+            median_nanos: 39e6,
+            stddev_nanos: 1e6,
+        });
+
+        let options = "<!--- options: columns=median,percent sort=cost highlight=1 --->";
+        let mut s = format!("foo\n{MARKER}\n{options}\n{MARKER}\nbaz");
+        update_content(
+            &mut s,
+            Year::primary(),
+            None,
+            timings,
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+
+        let expected = [
+            "foo",
+            MARKER,
+            options,
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 1 median | Part 2 | Part 2 median | % of total |",
+            "| :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| **[Day 4](./src/bin/04.rs)** | `40.0ms` | `39.0ms` | `50.0ms` | `-` | `47.4%` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `-` | `40.0ms` | `-` | `36.8%` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `-` | `20.0ms` | `-` | `15.8%` |",
+            "",
+            "**Total: 190.00ms**",
+            MARKER,
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn applies_default_options() {
+        let defaults = TableOptions {
+            sort: SortOrder::Cost,
+            ..TableOptions::default()
+        };
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(
+            &mut s,
+            Year::primary(),
+            None,
+            get_mock_timings(),
+            190.0,
+            &defaults,
+        )
+        .unwrap();
+        let day_4 = s.find("[Day 4]").unwrap();
+        assert!(day_4 < s.find("[Day 1]").unwrap());
+        // the defaults are stored with the table, so later changes of the config do not affect it.
+        assert!(s.contains("<!--- options: columns= sort=cost highlight=0 --->"));
+    }
+
+    #[test]
+    fn updates_named_tables() {
+        let marker = "<!--- benchmarking table laptop --->";
+        let mut s = format!("{MARKER}{MARKER}\n{marker}{marker}");
+        update_content(
+            &mut s,
+            Year::primary(),
+            Some("laptop"),
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+        assert!(s.contains("## Benchmarks (laptop)"));
+        assert!(s.starts_with(&format!("{MARKER}{MARKER}\n{marker}\n")));
+    }
 }
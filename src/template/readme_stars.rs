@@ -0,0 +1,262 @@
+/// Module that updates the readme with the collected stars, replacing the table of the `aoc-readme-stars` action.
+/// Stars are either derived from the locally recorded answers or from a saved copy of the calendar page.
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::{Day, PuzzleId, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Number of collected stars per day.
+pub type Stars = BTreeMap<Day, u8>;
+
+fn get_marker(year: Year) -> String {
+    if year.is_primary() {
+        MARKER.into()
+    } else {
+        format!("<!--- advent_readme_stars table {year} --->")
+    }
+}
+
+/// Count the stars of a year from the recorded answers.
+/// The second star of the last day is awarded once every other star of the year has been collected.
+pub fn from_answers(answers: &Answers, year: Year) -> Stars {
+    let mut stars: Stars = year
+        .days()
+        .map(|day| {
+            let parts = PuzzleId::new(year, day).part_count();
+            let solved = (1..=parts).filter(|p| answers.is_solved(day, *p)).count();
+            #[allow(clippy::cast_possible_truncation)]
+            (day, solved as u8)
+        })
+        .filter(|(_, count)| *count > 0)
+        .collect();
+
+    let last_day = year.last_day();
+    let others_complete = year
+        .days()
+        .filter(|day| *day != last_day)
+        .all(|day| stars.get(&day) == Some(&2));
+
+    if others_complete {
+        if let Some(count) = stars.get_mut(&last_day) {
+            *count = 2;
+        }
+    }
+
+    stars
+}
+
+/// Count the stars of a year from the HTML of its calendar page, e.g. `https://adventofcode.com/2023`.
+/// Every day of the calendar is labelled like `aria-label="Day 1, two stars"`.
+pub fn from_calendar(html: &str) -> Result<Stars, Error> {
+    let prefix = "aria-label=\"Day ";
+    let mut stars = Stars::new();
+
+    if !html.contains(prefix) {
+        return Err(Error::Parser(
+            "Could not find any days in the calendar page.".into(),
+        ));
+    }
+
+    for (pos, _) in html.match_indices(prefix) {
+        let label = html[pos + prefix.len()..]
+            .split('"')
+            .next()
+            .unwrap_or_default();
+
+        let (day, count) = label.split_once(',').unwrap_or((label, ""));
+        let day: Day = day
+            .trim()
+            .parse()
+            .map_err(|_| Error::Parser(format!("Invalid day in calendar label `{label}`.")))?;
+
+        let count = match count.trim() {
+            "two stars" => 2,
+            "one star" => 1,
+            _ => 0,
+        };
+
+        if count > 0 {
+            stars.insert(day, count);
+        }
+    }
+
+    Ok(stars)
+}
+
+fn construct_table(year: Year, stars: &Stars) -> String {
+    let marker = get_marker(year);
+
+    let mut lines: Vec<String> = vec![
+        marker.clone(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (day, count) in stars {
+        let star = |part: u8| if *count >= part { "⭐" } else { " " };
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} |",
+            day.into_inner(),
+            day.into_inner(),
+            star(1),
+            star(2)
+        ));
+    }
+
+    lines.push(marker);
+
+    lines.join("\n")
+}
+
+/// Read the stars of an existing table, e.g. one that was generated before answers were recorded locally.
+fn read_table(table: &str) -> Stars {
+    table
+        .lines()
+        .filter_map(|line| {
+            let day = line
+                .strip_prefix("| [Day ")?
+                .split(']')
+                .next()?
+                .parse()
+                .ok()?;
+            #[allow(clippy::cast_possible_truncation)]
+            let count = line.matches('⭐').count() as u8;
+            (count > 0).then_some((day, count))
+        })
+        .collect()
+}
+
+/// Stars are never taken away, so the table keeps the stars it already has.
+fn update_content(s: &mut String, year: Year, stars: &Stars) -> Result<Stars, Error> {
+    let positions = locate_table(s, &get_marker(year))?;
+
+    let mut merged = read_table(&s[positions.pos_start..positions.pos_end]);
+    for (day, count) in stars {
+        let entry = merged.entry(*day).or_default();
+        *entry = (*entry).max(*count);
+    }
+
+    let table = construct_table(year, &merged);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(merged)
+}
+
+/// Add stars to the stars table of a year in the readme. Returns the stars of the updated table.
+pub fn update(year: Year, stars: &Stars) -> Result<Stars, Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let merged = update_content(&mut readme, year, stars)?;
+    fs::write(path, &readme)?;
+    Ok(merged)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_answers, from_calendar, update_content, Stars, MARKER};
+    use crate::day;
+    use crate::template::answers::Answers;
+    use crate::template::Year;
+
+    #[test]
+    fn counts_stars_from_answers() {
+        let year = Year::new(2025).unwrap();
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "1");
+        answers.record(day!(1), 2, "2");
+        answers.record(day!(3), 1, "3");
+        answers.record(day!(12), 1, "12");
+
+        let stars = from_answers(&answers, year);
+        assert_eq!(stars.get(&day!(1)), Some(&2));
+        assert_eq!(stars.get(&day!(2)), None);
+        assert_eq!(stars.get(&day!(3)), Some(&1));
+        assert_eq!(stars.get(&day!(12)), Some(&1));
+    }
+
+    #[test]
+    fn awards_last_star_for_complete_years() {
+        let year = Year::new(2025).unwrap();
+        let mut answers = Answers::default();
+        for day in year.days() {
+            answers.record(day, 1, "1");
+            answers.record(day, 2, "2");
+        }
+
+        let stars = from_answers(&answers, year);
+        assert_eq!(stars.len(), 12);
+        assert!(stars.values().all(|count| *count == 2));
+    }
+
+    #[test]
+    fn counts_stars_from_calendar() {
+        let html = r#"
+            <a aria-label="Day 1, two stars" href="/2023/day/1" class="calendar-day1 calendar-verycomplete">
+            <a aria-label="Day 2, one star" href="/2023/day/2" class="calendar-day2 calendar-complete">
+            <a aria-label="Day 3" href="/2023/day/3" class="calendar-day3">
+        "#;
+
+        let stars = from_calendar(html).unwrap();
+        assert_eq!(stars.get(&day!(1)), Some(&2));
+        assert_eq!(stars.get(&day!(2)), Some(&1));
+        assert_eq!(stars.get(&day!(3)), None);
+    }
+
+    #[test]
+    fn rejects_pages_without_calendar() {
+        assert!(from_calendar("<html>login</html>").is_err());
+    }
+
+    #[test]
+    fn formats_stars_table() {
+        let mut stars = Stars::new();
+        stars.insert(day!(1), 2);
+        stars.insert(day!(2), 1);
+
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\nbar");
+        update_content(&mut s, Year::primary(), &stars).unwrap();
+
+        let year = Year::primary();
+        let expected = [
+            "foo".to_string(),
+            MARKER.into(),
+            format!("## {year} Results"),
+            String::new(),
+            "| Day | Part 1 | Part 2 |".into(),
+            "| :---: | :---: | :---: |".into(),
+            format!("| [Day 1](https://adventofcode.com/{year}/day/1) | ⭐ | ⭐ |"),
+            format!("| [Day 2](https://adventofcode.com/{year}/day/2) | ⭐ |   |"),
+            MARKER.into(),
+            "bar".into(),
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn keeps_existing_stars() {
+        let year = Year::primary();
+        let mut s = format!("{MARKER}\n{MARKER}");
+        let mut stars = Stars::new();
+        stars.insert(day!(1), 2);
+        stars.insert(day!(2), 1);
+        update_content(&mut s, year, &stars).unwrap();
+
+        let mut stars = Stars::new();
+        stars.insert(day!(2), 2);
+        stars.insert(day!(3), 1);
+        let merged = update_content(&mut s, year, &stars).unwrap();
+
+        assert_eq!(merged.get(&day!(1)), Some(&2));
+        assert_eq!(merged.get(&day!(2)), Some(&2));
+        assert_eq!(merged.get(&day!(3)), Some(&1));
+        assert!(s.contains(&format!(
+            "| [Day 1](https://adventofcode.com/{year}/day/1) | ⭐ | ⭐ |"
+        )));
+    }
+}
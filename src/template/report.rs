@@ -0,0 +1,368 @@
+/// Module that renders a static HTML report of the answers and benchmark timings of a year.
+/// The report is a single file without external assets, so it can be shared as-is.
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::{Component, Path};
+use std::{env, fs, io};
+
+use crate::template::answers::Answers;
+use crate::template::history::History;
+use crate::template::host::Host;
+use crate::template::readme_benchmarks::format_nanos;
+use crate::template::readme_stars::from_answers;
+use crate::template::timings::{PartStats, Timing, Timings};
+use crate::template::year::civil_from_days;
+use crate::template::{Day, PuzzleId, Year};
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2rem;color:#222}\
+table{border-collapse:collapse}\
+th,td{padding:.3rem .6rem;border-bottom:1px solid #ddd;text-align:left;vertical-align:middle}\
+td.num{text-align:right;font-family:monospace}\
+.stats{color:#888;font-size:.8em}\
+.masked{color:#aaa}\
+dt{font-weight:bold}dd{margin:0 0 .4rem 0}";
+
+const SPARKLINE_WIDTH: f64 = 100.0;
+const SPARKLINE_HEIGHT: f64 = 20.0;
+
+/// Everything that is shown in a report.
+pub struct Report<'a> {
+    pub year: Year,
+    pub timings: &'a Timings,
+    pub answers: &'a Answers,
+    pub history: &'a History,
+    pub host: &'a Host,
+    /// Seconds since the unix epoch.
+    pub generated_at: u64,
+    /// Hide the answers, e.g. when the report is shared with people who are still solving.
+    pub mask_answers: bool,
+    /// Prefix of the links to the solution files, relative to the report.
+    pub source_prefix: String,
+}
+
+impl Report<'_> {
+    /// Render the report as an HTML document.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let year = self.year;
+        let mut html = String::new();
+
+        writeln!(html, "<!DOCTYPE html>").unwrap();
+        writeln!(html, "<html lang=\"en\">").unwrap();
+        writeln!(html, "<head>").unwrap();
+        writeln!(html, "<meta charset=\"utf-8\">").unwrap();
+        writeln!(html, "<title>Advent of Code {year}</title>").unwrap();
+        writeln!(html, "<style>{STYLE}</style>").unwrap();
+        writeln!(html, "</head>").unwrap();
+        writeln!(html, "<body>").unwrap();
+        writeln!(html, "<h1>Advent of Code {year}</h1>").unwrap();
+
+        writeln!(html, "<dl>").unwrap();
+        for (term, value) in [
+            ("Generated", format_timestamp(self.generated_at)),
+            ("OS", self.host.os.clone()),
+            (
+                "CPU",
+                format!("{} ({} cores)", self.host.cpu, self.host.cores),
+            ),
+            (
+                "Total",
+                format!(
+                    "{} ({} stars)",
+                    format_nanos(self.timings.total_millis() * 1_000_000_f64),
+                    from_answers(self.answers, self.year)
+                        .values()
+                        .map(|count| u32::from(*count))
+                        .sum::<u32>()
+                ),
+            ),
+        ] {
+            writeln!(html, "<dt>{term}</dt><dd>{}</dd>", escape(&value)).unwrap();
+        }
+        writeln!(html, "</dl>").unwrap();
+
+        writeln!(html, "<table>").unwrap();
+        writeln!(
+            html,
+            "<tr><th>Day</th><th>Answer 1</th><th>Answer 2</th><th>Part 1</th><th>Part 2</th><th>Parse</th><th>Total</th><th>History</th><th>Source</th></tr>"
+        )
+        .unwrap();
+
+        for day in self.days() {
+            self.render_day(&mut html, day);
+        }
+
+        writeln!(html, "</table>").unwrap();
+        writeln!(html, "</body>").unwrap();
+        writeln!(html, "</html>").unwrap();
+        html
+    }
+
+    /// Days that were either solved or timed.
+    fn days(&self) -> BTreeSet<Day> {
+        self.timings
+            .data
+            .iter()
+            .map(|t| t.day)
+            .chain(self.answers.data.iter().map(|a| a.day))
+            .filter(|day| self.year.has_day(*day))
+            .collect()
+    }
+
+    fn render_day(&self, html: &mut String, day: Day) {
+        let puzzle = PuzzleId::new(self.year, day);
+        let timing = self.timings.data.iter().find(|t| t.day == day);
+
+        write!(
+            html,
+            "<tr><td><a href=\"https://adventofcode.com/{}/day/{}\">Day {day}</a></td>",
+            self.year,
+            day.into_inner()
+        )
+        .unwrap();
+
+        for part in 1..=2 {
+            let answer = match self.answers.get(day, part) {
+                _ if part > puzzle.part_count() => "n/a".into(),
+                Some(_) if self.mask_answers => "<span class=\"masked\">hidden</span>".into(),
+                Some(answer) => format!("<code>{}</code>", escape(answer)),
+                None => "-".into(),
+            };
+            write!(html, "<td>{answer}</td>").unwrap();
+        }
+
+        for part in 1..=2 {
+            write!(html, "<td class=\"num\">{}</td>", format_part(timing, part)).unwrap();
+        }
+
+        let parse = timing
+            .and_then(|t| t.stats.parse_nanos)
+            .map_or_else(|| "-".into(), format_nanos);
+        write!(html, "<td class=\"num\">{parse}</td>").unwrap();
+
+        let total = match timing {
+            Some(t) if t.timed_out => "timed out".into(),
+            Some(t) => format_nanos(t.total_nanos),
+            None => "-".into(),
+        };
+        write!(html, "<td class=\"num\">{total}</td>").unwrap();
+
+        write!(html, "<td>{}</td>", sparkline(&self.history.series(day))).unwrap();
+
+        let bin_path = puzzle.bin_path();
+        let source = bin_path.trim_start_matches("./");
+        writeln!(
+            html,
+            "<td><a href=\"{}{source}\">{source}</a></td></tr>",
+            self.source_prefix
+        )
+        .unwrap();
+    }
+}
+
+fn format_part(timing: Option<&Timing>, part: u8) -> String {
+    let Some(timing) = timing else {
+        return "-".into();
+    };
+
+    let Some(nanos) = timing.part_nanos(part) else {
+        return "-".into();
+    };
+
+    let stats = match part {
+        1 => timing.stats.part_1.as_ref(),
+        _ => timing.stats.part_2.as_ref(),
+    };
+
+    match stats {
+        Some(PartStats {
+            samples,
+            median_nanos,
+            stddev_nanos,
+        }) => format!(
+            "{}<br><span class=\"stats\">median {}, stddev {} @ {samples} samples</span>",
+            format_nanos(nanos),
+            format_nanos(*median_nanos),
+            format_nanos(*stddev_nanos)
+        ),
+        None => format_nanos(nanos),
+    }
+}
+
+/// Inline SVG line of a series of run times, scaled between its minimum and maximum.
+#[allow(clippy::cast_precision_loss)]
+fn sparkline(series: &[f64]) -> String {
+    if series.len() < 2 {
+        return "-".into();
+    }
+
+    let min = series.iter().copied().fold(f64::MAX, f64::min);
+    let max = series.iter().copied().fold(f64::MIN, f64::max);
+    let range = if max > min { max - min } else { 1.0 };
+    let step = SPARKLINE_WIDTH / (series.len() - 1) as f64;
+
+    let points: Vec<String> = series
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let x = i as f64 * step;
+            let y = SPARKLINE_HEIGHT - 1.0 - (v - min) / range * (SPARKLINE_HEIGHT - 2.0);
+            format!("{x:.1},{y:.1}")
+        })
+        .collect();
+
+    format!(
+        "<svg width=\"{SPARKLINE_WIDTH}\" height=\"{SPARKLINE_HEIGHT}\"><title>{} to {} over {} runs</title><polyline points=\"{}\" fill=\"none\" stroke=\"#9999cc\" stroke-width=\"1.5\"/></svg>",
+        format_nanos(min),
+        format_nanos(max),
+        series.len(),
+        points.join(" ")
+    )
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn format_timestamp(secs: u64) -> String {
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let secs_of_day = secs % 86_400;
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02} UTC",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60
+    )
+}
+
+/// Prefix that leads from the directory of a report back to the project root.
+/// Reports outside of the project link to the absolute path of the solutions instead.
+#[must_use]
+pub fn source_prefix(report_path: &Path) -> String {
+    let parent = report_path.parent().unwrap_or(Path::new(""));
+
+    if parent
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        parent
+            .components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .map(|_| "../")
+            .collect()
+    } else {
+        env::current_dir()
+            .map(|dir| format!("file://{}/", dir.display()))
+            .unwrap_or_default()
+    }
+}
+
+/// Write a report to a file.
+pub fn write(path: &Path, report: &Report) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, report.render())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{escape, format_timestamp, source_prefix, sparkline, Report};
+    use crate::day;
+    use crate::template::answers::Answers;
+    use crate::template::history::History;
+    use crate::template::host::Host;
+    use crate::template::timings::{PartStats, Timing, TimingStats, Timings};
+    use crate::template::Year;
+
+    fn get_mock_host() -> Host {
+        Host {
+            os: "Linux (x86_64)".into(),
+            cpu: "Test CPU".into(),
+            cores: 4,
+        }
+    }
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1_nanos: Some(10e6),
+                part_2_nanos: Some(20e6),
+                total_nanos: 30_000_000_f64,
+                timed_out: false,
+                stats: TimingStats {
+                    part_1: Some(PartStats {
+                        samples: 10,
+                        median_nanos: 9_000_000_f64,
+                        stddev_nanos: 1_000_000_f64,
+                    }),
+                    ..Default::default()
+                },
+                meta: None,
+            }],
+        }
+    }
+
+    fn render(mask_answers: bool) -> String {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "<42>");
+        answers.record(day!(2), 1, "7");
+
+        let mut history = History::default();
+        history.record(1, &get_mock_timings());
+        history.record(2, &get_mock_timings());
+
+        Report {
+            year: Year::new(2023).unwrap(),
+            timings: &get_mock_timings(),
+            answers: &answers,
+            history: &history,
+            host: &get_mock_host(),
+            generated_at: 1_701_388_800,
+            mask_answers,
+            source_prefix: String::new(),
+        }
+        .render()
+    }
+
+    #[test]
+    fn renders_report() {
+        let html = render(false);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<dd>2023-12-01 00:00 UTC</dd>"));
+        assert!(html.contains("<dd>Test CPU (4 cores)</dd>"));
+        assert!(html.contains("<code>&lt;42&gt;</code>"));
+        assert!(html.contains("median 9.0ms, stddev 1.0ms @ 10 samples"));
+        assert!(html.contains("<a href=\"src/bin/02.rs\">"));
+        assert_eq!(html.matches("<tr><td>").count(), 2);
+        assert_eq!(html.matches("<polyline").count(), 1);
+    }
+
+    #[test]
+    fn masks_answers() {
+        let html = render(true);
+        assert!(!html.contains("&lt;42&gt;"));
+        assert_eq!(html.matches("class=\"masked\"").count(), 2);
+    }
+
+    #[test]
+    fn renders_sparklines() {
+        assert_eq!(sparkline(&[1.0]), "-");
+        let svg = sparkline(&[1_000.0, 3_000.0, 2_000.0]);
+        assert!(svg.contains("points=\"0.0,19.0 50.0,1.0 100.0,10.0\""));
+    }
+
+    #[test]
+    fn formats_helpers() {
+        assert_eq!(escape("a < \"b\" & c"), "a &lt; &quot;b&quot; &amp; c");
+        assert_eq!(format_timestamp(86_400 + 3_660), "1970-01-02 01:01 UTC");
+        assert_eq!(source_prefix(Path::new("report.html")), "");
+        assert_eq!(source_prefix(Path::new("out/docs/report.html")), "../../");
+    }
+}
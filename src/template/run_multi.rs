@@ -1,40 +1,198 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io,
+    path::Path,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::runner::BenchConfig;
+use crate::template::{Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    readme_benchmarks::{format_bytes, format_nanos},
+    timings::{Timing, TimingMeta, TimingStats, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Limits applied to every solution run by [`run_multi`].
+#[derive(Clone, Debug, Default)]
+pub struct Limits {
+    /// Timeout applied to days without an override.
+    pub timeout: Option<Duration>,
+    /// Per-day timeouts, taking precedence over `timeout`.
+    pub day_timeouts: HashMap<Day, Duration>,
+    /// Maximum size of the address space of a solution process, in megabytes.
+    pub memory_limit_mb: Option<u64>,
+}
+
+impl Limits {
+    #[must_use]
+    pub fn timeout_for(&self, day: Day) -> Option<Duration> {
+        self.day_timeouts.get(&day).copied().or(self.timeout)
+    }
+}
+
+/// Options of a [`run_multi`] invocation.
+#[derive(Clone, Debug)]
+pub struct RunOptions {
+    pub is_release: bool,
+    /// Bench the solutions and collect their timings.
+    pub is_timed: bool,
+    /// Count the heap allocations of each part of timed runs.
+    pub track_allocations: bool,
+    /// Number of days that run concurrently.
+    pub jobs: usize,
+    /// Only run a single part of each day.
+    pub part: Option<u8>,
+    /// Settings of the benchmark loop of timed runs.
+    pub bench: BenchConfig,
+    /// Size of the rayon thread pool of each solution, see [`runner::configure_threads`](crate::template::runner::configure_threads).
+    pub threads: Option<usize>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            is_release: false,
+            is_timed: false,
+            track_allocations: false,
+            jobs: 1,
+            part: None,
+            bench: BenchConfig::default(),
+            threads: None,
+        }
+    }
+}
+
+/// Run the solutions for a set of days, printing their output in day order.
+///
+/// With `jobs` larger than one, days run concurrently and their output is buffered until all previous
+/// days are complete. Timed runs always run sequentially, so that solutions do not interfere with each
+/// others' measurements.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    year: Year,
+    limits: &Limits,
+    options: &RunOptions,
+) -> Option<Timings> {
+    let RunOptions {
+        is_release,
+        is_timed,
+        jobs,
+        ..
+    } = *options;
+
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    // build all scaffolded solutions upfront, so that compilation does not count towards timeouts.
+    let scaffolded: Vec<PuzzleId> = days
+        .iter()
+        .map(|day| PuzzleId::new(year, *day))
+        .filter(|puzzle| Path::new(&puzzle.bin_path()).exists())
+        .collect();
+
+    if !scaffolded.is_empty() {
+        if let Err(e) = child_commands::build_solutions(&scaffolded, is_release) {
+            eprintln!("Failed to build solutions: {e}");
+            process::exit(1);
+        }
+    }
+
     let mut need_space = false;
+    let mut print_header = |day: Day| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    };
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let mut report = |day: Day, output: Result<child_commands::SolutionOutput, Error>| {
+        let output = match output {
+            Ok(output) => output,
+            // e.g. the memory limit could not be applied, the remaining days are still run.
+            Err(e) => {
+                println!("ERROR (failed to run solution: {e})");
+                return;
+            }
+        };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        if output.timed_out {
+            println!(
+                "TIMEOUT (killed after {:?})",
+                limits.timeout_for(day).unwrap_or_default()
+            );
+            timings.push(Timing {
+                day,
+                part_1_nanos: None,
+                part_2_nanos: None,
+                total_nanos: 0_f64,
+                timed_out: true,
+                stats: TimingStats::default(),
+                meta: None,
+            });
+        } else if output.lines.is_empty() {
+            println!("Not solved.");
+        } else {
+            let mut val = child_commands::parse_exec_time(&output.lines, day);
+            if let Some(usage) = output.usage {
+                if is_timed {
+                    println!(
+                        "Resources: {} user, {} sys, {:.1}× wall, max RSS {}",
+                        format_nanos(usage.user_nanos),
+                        format_nanos(usage.system_nanos),
+                        usage.parallelism(),
+                        format_bytes(usage.max_rss_bytes)
+                    );
+                }
+                val.stats.usage = Some(usage);
+            }
+            timings.push(val);
+        }
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    if is_timed || jobs <= 1 {
+        for day in days {
+            print_header(day);
+            let puzzle = PuzzleId::new(year, day);
+            let output = child_commands::run_solution(puzzle, options, limits, true);
+            report(day, output);
+        }
+    } else {
+        let puzzles: Vec<PuzzleId> = days.iter().map(|day| PuzzleId::new(year, *day)).collect();
+
+        run_parallel(&puzzles, options, limits, |puzzle, output| {
+            let day = puzzle.day;
+            print_header(day);
+            if let Ok(output) = &output {
+                for line in &output.lines {
+                    println!("{line}");
+                }
+                for line in &output.stderr {
+                    eprintln!("{line}");
+                }
             }
+            report(day, output);
         });
+    }
 
     if is_timed {
+        let meta = TimingMeta::detect(is_release);
+        for timing in &mut timings {
+            timing.meta = Some(meta.clone());
+        }
+
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -46,9 +204,59 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Run days on `jobs` worker threads, with buffered output.
+/// `on_complete` is called in day order, as soon as a day and all days before it have finished.
+fn run_parallel(
+    puzzles: &[PuzzleId],
+    options: &RunOptions,
+    limits: &Limits,
+    mut on_complete: impl FnMut(PuzzleId, Result<child_commands::SolutionOutput, Error>),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.min(puzzles.len()) {
+            let sender = sender.clone();
+            let next = &next;
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(&puzzle) = puzzles.get(i) else {
+                    break;
+                };
+
+                let output = child_commands::run_solution(puzzle, options, limits, false);
+
+                if sender.send((i, output)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // drop the original sender, so that the receiver terminates once all workers are done.
+        drop(sender);
+
+        let mut pending: BTreeMap<usize, Result<child_commands::SolutionOutput, Error>> =
+            BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (i, output) in receiver {
+            pending.insert(i, output);
+
+            while let Some(output) = pending.remove(&next_to_print) {
+                on_complete(puzzles[next_to_print], output);
+                next_to_print += 1;
+            }
+        }
+    });
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    BuildFailed,
+    BadExitStatus,
     IO(io::Error),
 }
 
@@ -58,134 +266,495 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read output of solution"),
+            Error::BuildFailed => write!(f, "cargo build exited with a non-zero status"),
+            Error::BadExitStatus => write!(f, "solution exited with a non-zero status"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, Limits, RunOptions, TimingStats};
+    use crate::template::alloc::AllocStats;
+    use crate::template::timings::{parse_bytes, parse_duration, PartStats, ResourceUsage};
+    use crate::template::{Day, PuzzleId};
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        env,
+        io::{self, BufRead, BufReader},
+        path::{Path, PathBuf},
+        process::{Child, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// Build the solution bins for the given puzzles, so they can be invoked without going through `cargo run`.
+    pub fn build_solutions(puzzles: &[PuzzleId], is_release: bool) -> Result<(), Error> {
+        let bin_names: Vec<String> = puzzles.iter().map(|p| p.bin_name()).collect();
+        let mut args = vec!["build", "--quiet"];
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        for bin_name in &bin_names {
+            args.push("--bin");
+            args.push(bin_name);
+        }
 
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
+        let status = Command::new("cargo").args(&args).status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::BuildFailed)
+        }
+    }
+
+    /// Path of the compiled solution bin for a given puzzle.
+    #[must_use]
+    pub fn get_target_path(puzzle: PuzzleId, is_release: bool) -> PathBuf {
+        let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+        get_target_path_in(Path::new(&target_dir), puzzle, is_release)
+    }
+
+    /// Path of the compiled solution bin for a given puzzle, in a specific target directory.
+    #[must_use]
+    pub fn get_target_path_in(target_dir: &Path, puzzle: PuzzleId, is_release: bool) -> PathBuf {
+        let profile = if is_release { "release" } else { "debug" };
+        target_dir
+            .join(profile)
+            .join(format!("{}{}", puzzle.bin_name(), env::consts::EXE_SUFFIX))
+    }
+
+    /// Run a compiled solution bin with the given arguments, returning its stdout lines without printing them.
+    pub fn run_bin(
+        puzzle: PuzzleId,
+        is_release: bool,
+        args: &[&str],
+    ) -> Result<Vec<String>, Error> {
+        let output = Command::new(get_target_path(puzzle, is_release))
+            .args(args)
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::BadExitStatus);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(ToString::to_string)
+            .collect())
+    }
+
+    /// List the names of the solution variants a compiled bin provides.
+    pub fn list_variants(puzzle: PuzzleId, is_release: bool) -> Result<Vec<String>, Error> {
+        run_bin(puzzle, is_release, &["--list-variants"])
+    }
+
+    /// Extract the answer for each part from the output of a solution bin.
+    /// Unsolved parts are reported as `✖`, answers that span multiple lines are joined by newlines.
+    pub fn parse_answers(output: &[String]) -> Vec<(u8, String)> {
+        let mut answers: Vec<(u8, String)> = vec![];
+        let mut is_multiline = false;
+
+        for line in output {
+            // intermediate results are overwritten with a carriage return, only keep the final result.
+            let line = strip_ansi(line.rsplit('\r').next().unwrap_or_default());
+
+            let part = line
+                .strip_prefix("Part ")
+                .and_then(|l| l.split_once(": "))
+                .and_then(|(part, rest)| Some((part.parse::<u8>().ok()?, rest)));
+
+            if let Some((part, rest)) = part {
+                // multi-line answers are printed as `Part 1: ▼ (duration)`, followed by the answer.
+                is_multiline = rest.starts_with('▼');
+                let answer = if is_multiline {
+                    String::new()
+                } else {
+                    strip_duration(rest).trim().to_string()
+                };
+                answers.push((part, answer));
+            } else if let (true, Some((_, answer))) = (is_multiline, answers.last_mut()) {
+                if !answer.is_empty() {
+                    answer.push('\n');
+                }
+                answer.push_str(&line);
+            }
+        }
+
+        for (_, answer) in &mut answers {
+            *answer = answer.trim_end().to_string();
+        }
+
+        answers
+    }
+
+    fn strip_ansi(s: &str) -> String {
+        let mut result = String::with_capacity(s.len());
+        let mut chars = s.chars();
+
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                // skip the escape sequence up to and including its final letter.
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            } else {
+                result.push(c);
+            }
+        }
+
+        result
+    }
+
+    fn strip_duration(s: &str) -> &str {
+        match s.rfind(" (") {
+            Some(i) if s.ends_with(')') => &s[..i],
+            _ => s,
+        }
+    }
+
+    /// Wait for a child process to exit. If it is still running after `timeout`, it is killed and `None` is returned.
+    pub fn wait_with_timeout(
+        child: &mut Child,
+        timeout: Duration,
+    ) -> io::Result<Option<ExitStatus>> {
+        let start = Instant::now();
+
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Some(status));
+            }
+
+            if start.elapsed() >= timeout {
+                child.kill()?;
+                child.wait()?;
+                return Ok(None);
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Wait for a child process that was spawned at `started` to exit and collect its resource usage via `wait4`.
+    /// If it is still running after `timeout`, it is killed and `None` is returned.
+    #[cfg(unix)]
+    fn wait_with_usage(
+        child: &mut Child,
+        started: Instant,
+        timeout: Option<Duration>,
+    ) -> io::Result<Option<ResourceUsage>> {
+        #[allow(clippy::cast_possible_wrap)]
+        let pid = child.id() as libc::pid_t;
+        let options = if timeout.is_some() { libc::WNOHANG } else { 0 };
+
+        loop {
+            let mut status = 0;
+            // SAFETY: `rusage` is a plain C struct, for which all zeroes is a valid value.
+            let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+            // SAFETY: `pid` is a child of this process that has not been reaped yet.
+            let res = unsafe { libc::wait4(pid, &mut status, options, &mut usage) };
+
+            if res == -1 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err);
+            }
+
+            if res == pid {
+                #[allow(clippy::cast_precision_loss)]
+                let wall_nanos = started.elapsed().as_nanos() as f64;
+                return Ok(Some(resource_usage(&usage, wall_nanos)));
+            }
+
+            if timeout.is_some_and(|timeout| started.elapsed() >= timeout) {
+                child.kill()?;
+                child.wait()?;
+                return Ok(None);
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[cfg(unix)]
+    #[allow(clippy::cast_precision_loss, clippy::cast_sign_loss)]
+    fn resource_usage(usage: &libc::rusage, wall_nanos: f64) -> ResourceUsage {
+        let nanos = |t: libc::timeval| t.tv_sec as f64 * 1e9 + t.tv_usec as f64 * 1e3;
+
+        // `ru_maxrss` is reported in kilobytes on linux, but in bytes on macos.
+        let rss_factor = if cfg!(target_os = "macos") { 1 } else { 1024 };
+
+        ResourceUsage {
+            user_nanos: nanos(usage.ru_utime),
+            system_nanos: nanos(usage.ru_stime),
+            wall_nanos,
+            max_rss_bytes: (usage.ru_maxrss as u64).saturating_mul(rss_factor),
+        }
+    }
+
+    /// Output of a solution bin run by [`run_solution`].
+    pub struct SolutionOutput {
+        pub lines: Vec<String>,
+        /// Lines written to stderr. Only collected when the output is not echoed.
+        pub stderr: Vec<String>,
+        pub timed_out: bool,
+        /// Resources used by the solution process. Only collected on unix platforms.
+        pub usage: Option<ResourceUsage>,
+    }
+
+    /// Run the solution bin for a given puzzle. The bin needs to be built with [`build_solutions`] beforehand.
+    ///
+    /// With `echo`, output is forwarded to stdout/stderr while the solution runs.
+    /// Otherwise, it is buffered and returned in its entirety.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        options: &RunOptions,
+        limits: &Limits,
+        echo: bool,
+    ) -> Result<SolutionOutput, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(SolutionOutput {
+                lines: vec![],
+                stderr: vec![],
+                timed_out: false,
+                usage: None,
+            });
+        }
+
+        let mut cmd = Command::new(get_target_path(puzzle, options.is_release));
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            cmd.arg("--time");
+
+            if options.track_allocations {
+                cmd.arg("--alloc");
+            }
+
+            cmd.args(options.bench.to_args());
+        }
+
+        if let Some(part) = options.part {
+            cmd.arg("--part").arg(part.to_string());
+        }
+
+        if let Some(threads) = options.threads {
+            cmd.arg("--threads").arg(threads.to_string());
+        }
+
+        if let Some(limit) = limits.memory_limit_mb {
+            limit_memory(&mut cmd, limit);
         }
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        #[cfg(unix)]
+        let started = Instant::now();
+        let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let stdout = BufReader::new(child.stdout.take().ok_or(super::Error::BrokenPipe)?);
+        let stderr = BufReader::new(child.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let stderr_thread = thread::spawn(move || {
+            let mut buffered = vec![];
+            for line in stderr.lines().map_while(Result::ok) {
+                if echo {
+                    eprintln!("{line}");
+                } else {
+                    buffered.push(line);
+                }
+            }
+            buffered
+        });
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stdout_thread = thread::spawn(move || {
+            stdout
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| {
+                    if echo {
+                        println!("{line}");
+                    }
+                })
+                .collect::<Vec<String>>()
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        #[cfg(unix)]
+        let (timed_out, usage) =
+            match wait_with_usage(&mut child, started, limits.timeout_for(puzzle.day))? {
+                Some(usage) => (false, Some(usage)),
+                None => (true, None),
+            };
+
+        #[cfg(not(unix))]
+        let (timed_out, usage) = match limits.timeout_for(puzzle.day) {
+            Some(timeout) => (wait_with_timeout(&mut child, timeout)?.is_none(), None),
+            None => {
+                child.wait()?;
+                (false, None)
+            }
+        };
+
+        // the pipes are closed once the child exits or is killed, which ends both threads.
+        let lines = stdout_thread.join().map_err(|_| Error::BrokenPipe)?;
+        let stderr = stderr_thread.join().map_err(|_| Error::BrokenPipe)?;
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        Ok(SolutionOutput {
+            lines,
+            stderr,
+            timed_out,
+            usage,
+        })
+    }
+
+    /// Limit the address space of the child process via `setrlimit`.
+    #[cfg(unix)]
+    fn limit_memory(cmd: &mut Command, limit_mb: u64) {
+        use std::os::unix::process::CommandExt;
+
+        let bytes = limit_mb.saturating_mul(1024 * 1024);
+
+        // SAFETY: `setrlimit` is async-signal-safe and does not allocate.
+        unsafe {
+            cmd.pre_exec(move || {
+                let limit = libc::rlimit {
+                    rlim_cur: bytes as libc::rlim_t,
+                    rlim_max: bytes as libc::rlim_t,
+                };
+
+                if libc::setrlimit(libc::RLIMIT_AS, &limit) == 0 {
+                    Ok(())
+                } else {
+                    Err(io::Error::last_os_error())
+                }
+            });
+        }
+    }
 
-        Ok(output)
+    #[cfg(not(unix))]
+    fn limit_memory(_cmd: &mut Command, _limit_mb: u64) {
+        eprintln!("Memory limits are only supported on unix platforms, ignoring `--memory-limit`.");
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
-            part_1: None,
-            part_2: None,
+            part_1_nanos: None,
+            part_2_nanos: None,
             total_nanos: 0_f64,
+            timed_out: false,
+            stats: TimingStats::default(),
+            meta: None,
         };
 
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                if !l.contains(" samples") {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some((nanos, stats, alloc)) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, nanos, stats, alloc))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, nanos, stats, alloc)| {
+                if part.contains("Parse") {
+                    // parse times are already part of the run time of the parts.
+                    timings.stats.parse_nanos = Some(nanos);
+                    return;
+                }
+
                 if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+                    timings.part_1_nanos = Some(nanos);
+                    timings.stats.part_1 = stats;
+                    timings.stats.part_1_alloc = alloc;
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                    timings.part_2_nanos = Some(nanos);
+                    timings.stats.part_2 = stats;
+                    timings.stats.part_2_alloc = alloc;
                 }
 
                 timings.total_nanos += nanos;
+
+                if let Some(alloc) = alloc {
+                    let stats = &mut timings.stats;
+                    stats.allocations = Some(stats.allocations.unwrap_or(0) + alloc.allocations);
+                    stats.peak_memory_bytes =
+                        Some(stats.peak_memory_bytes.unwrap_or(0).max(alloc.peak_bytes));
+                }
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    /// Parse a timing like `(74.1ns @ 100 samples, median 70.0ns, stddev 5.0ns)` at the end of a line.
+    /// Runs with `--alloc` append allocations, e.g. `, allocations 3, allocated 1.5KiB, peak 1.0KiB`.
+    fn parse_time(line: &str) -> Option<(f64, Option<PartStats>, Option<AllocStats>)> {
+        let (_, timing) = line.rsplit_once('(')?;
+        let mut fields = timing.trim_end().trim_end_matches(')').split(", ");
+
+        let (str_timing, samples) = fields.next()?.split_once('@')?;
+        let parsed_timing = parse_duration(str_timing.trim())?;
+
+        let samples = samples.trim().strip_suffix(" samples")?.parse().ok()?;
+        let mut median_nanos = None;
+        let mut stddev_nanos = None;
+        let mut allocations = None;
+        let mut bytes = None;
+        let mut peak_bytes = None;
+
+        for field in fields {
+            if let Some(median) = field.strip_prefix("median ") {
+                median_nanos = parse_duration(median);
+            } else if let Some(stddev) = field.strip_prefix("stddev ") {
+                stddev_nanos = parse_duration(stddev);
+            } else if let Some(count) = field.strip_prefix("allocations ") {
+                allocations = count.parse().ok();
+            } else if let Some(size) = field.strip_prefix("allocated ") {
+                bytes = parse_bytes(size);
+            } else if let Some(size) = field.strip_prefix("peak ") {
+                peak_bytes = parse_bytes(size);
+            }
+        }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        let stats = median_nanos
+            .zip(stddev_nanos)
+            .map(|(median_nanos, stddev_nanos)| PartStats {
+                samples,
+                median_nanos,
+                stddev_nanos,
+            });
+
+        let alloc = match (allocations, bytes, peak_bytes) {
+            (Some(allocations), Some(bytes), Some(peak_bytes)) => Some(AllocStats {
+                allocations,
+                bytes,
+                peak_bytes,
+            }),
+            _ => None,
+        };
+
+        Some((parsed_timing, stats, alloc))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -204,7 +773,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time};
 
         use crate::day;
 
@@ -219,8 +788,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.part_1_nanos.unwrap(), 74.13_f64);
+            assert_approx_eq!(res.part_2_nanos.unwrap(), 74130000_f64);
         }
 
         #[test]
@@ -234,8 +803,49 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.part_1_nanos.unwrap(), 2000000000_f64);
+            assert_approx_eq!(res.part_2_nanos.unwrap(), 100000000_f64);
+        }
+
+        #[test]
+        fn parses_timing_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (1.5µs @ 200 samples, median 1.4µs, stddev 100.0ns)".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples, median 70.0ns, stddev 5.5ns)".into(),
+                    "Part 2: 10 (1.2ms)".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74.13_f64);
+            assert_approx_eq!(res.part_1_nanos.unwrap(), 74.13_f64);
+            assert_eq!(res.part_2_nanos, None);
+            assert_eq!(res.stats.parse_nanos, Some(1500_f64));
+
+            let stats = res.stats.part_1.unwrap();
+            assert_eq!(stats.samples, 100000);
+            assert_approx_eq!(stats.median_nanos, 70_f64);
+            assert_approx_eq!(stats.stddev_nanos, 5.5_f64);
+        }
+
+        #[test]
+        fn parses_allocations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples, median 70.0ns, stddev 5.5ns, allocations 3, allocated 1.5KiB, peak 1.0KiB)".into(),
+                    "Part 2: 10 (1.2ms @ 10 samples, median 1.1ms, stddev 1.0µs, allocations 10, allocated 512B, peak 2.0KiB)".into(),
+                ],
+                day!(1),
+            );
+
+            let alloc = res.stats.part_1_alloc.unwrap();
+            assert_eq!(alloc.allocations, 3);
+            assert_eq!(alloc.bytes, 1536);
+            assert_eq!(alloc.peak_bytes, 1024);
+            assert_eq!(res.stats.part_1.unwrap().samples, 100000);
+
+            assert_eq!(res.stats.allocations, Some(13));
+            assert_eq!(res.stats.peak_memory_bytes, Some(2048));
         }
 
         #[test]
@@ -249,8 +859,27 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_1_nanos.is_none(), true);
+            assert_eq!(res.part_2_nanos.is_none(), true);
+        }
+
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(&[
+                "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (74.13ns @ 100000 samples)"
+                    .into(),
+                "Part 2: ▼ \rPart 2: ▼ (1.2ms)".into(),
+                "#..".into(),
+                ".#.".into(),
+                "".into(),
+            ]);
+            assert_eq!(res, vec![(1, "42".into()), (2, "#..\n.#.".into())]);
+        }
+
+        #[test]
+        fn parses_missing_answers() {
+            let res = parse_answers(&["Part 1: ✖             ".into()]);
+            assert_eq!(res, vec![(1, "✖".into())]);
         }
     }
 }
@@ -2,103 +2,430 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::alloc::{self, AllocStats};
+use crate::template::config;
+use crate::template::readme_benchmarks::format_bytes;
+use crate::template::{stats, ANSI_BOLD};
+use crate::template::{PuzzleId, ANSI_ITALIC, ANSI_RESET};
+
+/// Name of the variant that runs the regular `part_one` and `part_two` functions.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// Returns the solution variant selected with `--variant <name>`, or [`DEFAULT_VARIANT`].
+#[must_use]
+pub fn selected_variant() -> String {
+    let args: Vec<String> = env::args().collect();
+
+    args.iter()
+        .position(|x| x == "--variant")
+        .and_then(|i| args.get(i + 1))
+        .map_or_else(|| DEFAULT_VARIANT.into(), Clone::clone)
+}
+
+/// Size the rayon thread pool with `--threads <n>`. Without the flag, rayon uses one thread per core.
+/// Must run before the first parallel iterator, since the global pool can only be configured once.
+pub fn configure_threads() {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(threads) = args
+        .iter()
+        .position(|x| x == "--threads")
+        .and_then(|i| args.get(i + 1))
+    else {
+        return;
+    };
+
+    let threads = match threads.parse::<usize>() {
+        Ok(threads) if threads > 0 => threads,
+        _ => {
+            eprintln!("`--threads` expects a positive number, got `{threads}`.");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+    {
+        eprintln!("Failed to configure the thread pool: {e}");
+        process::exit(1);
+    }
+}
+
+/// Bounds of the number of samples of a benchmark, regardless of the time budget and precision.
+const MIN_SAMPLES: usize = 10;
+const MAX_SAMPLES: usize = 100_000;
+
+/// Settings of the benchmark loop. Solution bins read them from `--warm-up <ms>`, `--bench-time <ms>` and
+/// `--precision <percent>`.
+///
+/// After the warm-up, samples are taken until the 95% confidence interval of the mean is within `precision`
+/// of the mean, or until the time budget is used up. Either way, at least 10 samples are taken.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchConfig {
+    /// Time the function runs before samples are taken, so that caches and branch predictors are warm.
+    pub warm_up: Duration,
+    /// Time after which sampling stops, even if the target precision was not reached.
+    pub budget: Duration,
+    /// Target half-width of the confidence interval, relative to the mean.
+    pub precision: f64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warm_up: Duration::from_millis(100),
+            budget: Duration::from_secs(1),
+            precision: 0.01,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Read the settings from the arguments of the process, falling back to the settings of `aoc.toml`.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let default = config::get().bench;
+        let millis =
+            |name: &str| arg_value(name).map(|v| Duration::from_millis(parse_arg(name, &v)));
+
+        BenchConfig {
+            warm_up: millis("--warm-up").unwrap_or(default.warm_up),
+            budget: millis("--bench-time").unwrap_or(default.budget),
+            precision: arg_value("--precision").map_or(default.precision, |v| {
+                parse_arg::<f64>("--precision", &v) / 100.0
+            }),
+        }
+    }
+
+    /// Arguments that pass the settings to a solution bin. Settings that match `aoc.toml` are omitted.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let default = config::get().bench;
+        let mut args = vec![];
+
+        if self.warm_up != default.warm_up {
+            args.extend(["--warm-up".into(), self.warm_up.as_millis().to_string()]);
+        }
+        if self.budget != default.budget {
+            args.extend(["--bench-time".into(), self.budget.as_millis().to_string()]);
+        }
+        if (self.precision - default.precision).abs() > f64::EPSILON {
+            args.extend(["--precision".into(), (self.precision * 100.0).to_string()]);
+        }
+
+        args
+    }
+}
+
+/// Value of a `--name <value>` argument of the process.
+fn arg_value(name: &str) -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let i = args.iter().position(|x| x == name)?;
+    args.get(i + 1).cloned()
+}
+
+fn parse_arg<T: std::str::FromStr>(name: &str, value: &str) -> T {
+    value.parse().unwrap_or_else(|_| {
+        eprintln!("Invalid value `{value}` for `{name}`.");
+        process::exit(1);
+    })
+}
+
+/// Print the names of all registered variants, one per line and without duplicates.
+pub fn print_variants(names: &[&str]) {
+    let mut printed: Vec<&str> = vec![];
+
+    for name in names {
+        if !printed.contains(name) {
+            println!("{name}");
+            printed.push(name);
+        }
+    }
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    if !is_part_selected(part) || part > puzzle.part_count() {
+        return;
+    }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats) = run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_stats(&stats));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if env::args().any(|x| x == "--export-samples") {
+        print_samples(part, &stats.timers);
     }
 }
 
+/// Time a parse function that is shared by both parts, see the `parse:` option of the `solution!` macro.
+/// Parse times are reported separately and are not added to the total of a day.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I) {
+    let (_, stats) = run_timed(func, input, |_| print!("Parse:"));
+    print!("\r");
+    println!("Parse:{}", format_stats(&stats));
+}
+
+/// Timing statistics of a benched function.
+struct Stats {
+    mean: Duration,
+    median: Duration,
+    stddev: Duration,
+    samples: u128,
+    outliers: Outliers,
+    /// Benched samples in the order they were taken.
+    timers: Vec<Duration>,
+    /// Heap allocations of the first run, if they were tracked with `--alloc`.
+    alloc: Option<AllocStats>,
+}
+
+/// Whether a part should run. Passing `--part <n>` to the binary only runs the given part.
+fn is_part_selected(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    args.iter()
+        .position(|x| x == "--part")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|p| p.parse::<u8>().ok())
+        .is_none_or(|selected| selected == part)
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+///  2. in release, the function is benched, see [`BenchConfig`].
+///
+/// Passing `--alloc` counts the heap allocations of the first run. Benched samples are not affected by this.
+fn run_timed<I: Clone, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Stats) {
+    let track_allocations = alloc::is_tracking_available() && env::args().any(|x| x == "--alloc");
+
     let timer = Instant::now();
-    let result = {
+    let (result, alloc) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        if track_allocations {
+            let (result, alloc) = alloc::track(|| func(input));
+            (result, Some(alloc))
+        } else {
+            (func(input), None)
+        }
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let mut stats = if std::env::args().any(|x| x == "--time") {
+        bench(func, input, base_time)
     } else {
-        (base_time, 1)
+        Stats {
+            mean: base_time,
+            median: base_time,
+            stddev: Duration::ZERO,
+            samples: 1,
+            outliers: Outliers::default(),
+            timers: vec![],
+            alloc: None,
+        }
     };
 
-    (result, run.0, run.1)
+    stats.alloc = alloc;
+
+    (result, stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: Duration) -> Stats {
+    let config = BenchConfig::from_args();
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    // the cold first run counts towards the warm-up, its sample is discarded.
+    let warm_up = Instant::now();
+    while base_time + warm_up.elapsed() < config.warm_up {
+        let cloned = input.clone();
+        black_box(func(black_box(cloned)));
+    }
 
     let mut timers: Vec<Duration> = vec![];
+    let mut running = RunningStats::default();
+    let sampling = Instant::now();
 
-    for _ in 0..bench_iterations {
+    loop {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
         black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+        let elapsed = timer.elapsed();
+
+        timers.push(elapsed);
+        running.push(elapsed);
+
+        if timers.len() >= MAX_SAMPLES {
+            break;
+        }
+
+        if timers.len() >= MIN_SAMPLES
+            && (running.relative_error() <= config.precision || sampling.elapsed() >= config.budget)
+        {
+            break;
+        }
+    }
+
+    let mut stats = compute_stats(&mut timers.clone());
+    stats.timers = timers;
+    stats
+}
+
+/// Mean and variance of the samples taken so far, updated in constant time per sample (Welford's algorithm).
+#[derive(Default)]
+struct RunningStats {
+    count: u32,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunningStats {
+    fn push(&mut self, sample: Duration) {
+        let x = sample.as_secs_f64() * 1e9;
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / f64::from(self.count);
+        self.m2 += delta * (x - self.mean);
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    /// Half-width of the 95% confidence interval of the mean, relative to the mean.
+    fn relative_error(&self) -> f64 {
+        if self.count < 2 || self.mean <= 0.0 {
+            return f64::INFINITY;
+        }
+
+        let count = f64::from(self.count);
+        let stddev = (self.m2 / (count - 1.0)).sqrt();
+        1.96 * stddev / count.sqrt() / self.mean
+    }
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
+/// Samples outside of the Tukey fences of a benchmark, classified like criterion does:
+/// mild outliers are more than 1.5 interquartile ranges away from the quartiles, severe ones more than 3.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Outliers {
+    mild: usize,
+    severe: usize,
+}
+
+/// Classify the outliers of samples that are sorted in ascending order.
+fn classify_outliers(sorted: &[Duration]) -> Outliers {
+    let mut outliers = Outliers::default();
+
+    if sorted.len() < 4 {
+        return outliers;
+    }
+
+    let nanos: Vec<f64> = sorted.iter().map(|t| t.as_secs_f64() * 1e9).collect();
+    let [low_severe, low_mild, high_mild, high_severe] = stats::tukey_fences(&nanos);
+
+    for x in nanos {
+        if x < low_severe || x > high_severe {
+            outliers.severe += 1;
+        } else if x < low_mild || x > high_mild {
+            outliers.mild += 1;
+        }
+    }
+
+    outliers
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn compute_stats(timers: &mut [Duration]) -> Stats {
+    timers.sort_unstable();
+
+    let samples = timers.len() as u128;
+    let mean = timers.iter().map(Duration::as_nanos).sum::<u128>() / samples;
+
+    let median = if timers.len().is_multiple_of(2) {
+        (timers[timers.len() / 2 - 1] + timers[timers.len() / 2]) / 2
+    } else {
+        timers[timers.len() / 2]
+    };
+
+    let variance = timers
         .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+        .map(|t| (t.as_nanos() as f64 - mean as f64).powi(2))
+        .sum::<f64>()
+        / samples as f64;
+
+    Stats {
+        mean: Duration::from_nanos(mean as u64),
+        median,
+        stddev: Duration::from_nanos(variance.sqrt() as u64),
+        samples,
+        outliers: classify_outliers(timers),
+        timers: vec![],
+        alloc: None,
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_stats(stats: &Stats) -> String {
+    let Stats {
+        mean,
+        median,
+        stddev,
+        samples,
+        outliers,
+        alloc,
+        ..
+    } = stats;
+
+    let outliers = if outliers.mild + outliers.severe > 0 {
+        format!(
+            ", {} mild and {} severe outliers",
+            outliers.mild, outliers.severe
+        )
+    } else {
+        String::new()
+    };
+
+    let alloc = alloc.map_or_else(String::new, |a| {
+        format!(
+            ", allocations {}, allocated {}, peak {}",
+            a.allocations,
+            format_bytes(a.bytes),
+            format_bytes(a.peak_bytes)
+        )
+    });
+
+    if *samples == 1 {
+        format!(" ({mean:.1?}{alloc})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({mean:.1?} @ {samples} samples, median {median:.1?}, stddev {stddev:.1?}{outliers}{alloc})"
+        )
     }
 }
 
+/// Print the benched samples of a part in nanoseconds, for the `cargo bench` harness.
+fn print_samples(part: u8, timers: &[Duration]) {
+    let nanos: Vec<String> = timers.iter().map(|t| t.as_nanos().to_string()).collect();
+    println!("Samples part {part}: {}", nanos.join(" "));
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -134,41 +461,50 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
 
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
+    use super::{classify_outliers, BenchConfig, Outliers, RunningStats};
+    use crate::template::config;
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    #[test]
+    fn tracks_relative_error() {
+        let mut stats = RunningStats::default();
+        stats.push(Duration::from_nanos(100));
+        assert!(stats.relative_error().is_infinite());
 
-    if part_submit != part {
-        return None;
+        for sample in nanos(&[90, 110, 100, 100]) {
+            stats.push(sample);
+        }
+        assert!((stats.mean - 100.0).abs() < 1e-9);
+        // stddev of 7.07ns over 5 samples.
+        assert!((stats.relative_error() - 0.062).abs() < 1e-3);
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn classifies_outliers() {
+        let samples = nanos(&[1, 100, 100, 101, 102, 102, 103, 104, 110, 1000]);
+        assert_eq!(classify_outliers(&samples), Outliers { mild: 1, severe: 2 });
+
+        let samples = nanos(&[100, 100, 101, 102]);
+        assert_eq!(classify_outliers(&samples), Outliers::default());
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn passes_non_default_settings() {
+        let default = config::get().bench;
+        assert!(default.to_args().is_empty());
+
+        let config = BenchConfig {
+            warm_up: Duration::from_millis(500),
+            precision: 0.05,
+            ..default
+        };
+        assert_eq!(config.to_args(), ["--warm-up", "500", "--precision", "5"]);
+    }
 }
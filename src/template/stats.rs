@@ -0,0 +1,231 @@
+/// Module with the statistics that are shared by the benchmark runner and the baseline comparisons.
+/// Samples are plain `f64` values, e.g. run times in nanoseconds.
+use std::f64::consts::PI;
+
+/// Arithmetic mean of samples, `0.0` for no samples.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn mean(samples: &[f64]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+/// Sample standard deviation, using Bessel's correction.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn std_dev(samples: &[f64]) -> f64 {
+    if samples.len() < 2 {
+        return 0.0;
+    }
+    let mean = mean(samples);
+    let sum = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+    (sum / (samples.len() - 1) as f64).sqrt()
+}
+
+/// Quantile of samples that are sorted in ascending order, interpolating linearly between neighbours.
+#[must_use]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor(), pos.ceil());
+    let at = |i: f64| sorted[i as usize];
+    at(lo) + (at(hi) - at(lo)) * (pos - lo)
+}
+
+/// Tukey fences of samples sorted in ascending order: the low severe, low mild, high mild and high severe fence.
+/// Mild fences are 1.5 interquartile ranges away from the quartiles, severe ones 3.
+#[must_use]
+pub fn tukey_fences(sorted: &[f64]) -> [f64; 4] {
+    let (q1, q3) = (quantile(sorted, 0.25), quantile(sorted, 0.75));
+    let iqr = q3 - q1;
+    [
+        q1 - 3.0 * iqr,
+        q1 - 1.5 * iqr,
+        q3 + 1.5 * iqr,
+        q3 + 3.0 * iqr,
+    ]
+}
+
+/// Median absolute deviation of samples sorted in ascending order, scaled to estimate the standard deviation of
+/// normally distributed samples, like criterion does.
+#[must_use]
+pub fn median_abs_dev(sorted: &[f64]) -> f64 {
+    let median = quantile(sorted, 0.5);
+    let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+    deviations.sort_unstable_by(f64::total_cmp);
+    quantile(&deviations, 0.5) * 1.4826
+}
+
+/// Result of Welch's t-test, which compares the means of two samples with possibly different variances.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TTest {
+    pub t: f64,
+    /// Degrees of freedom, approximated with the Welch–Satterthwaite equation.
+    pub df: f64,
+    /// Two-sided p-value of the hypothesis that both means are equal.
+    pub p: f64,
+}
+
+/// Welch's t-test of two samples. Returns `None` unless both samples have at least two values.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn welch_t_test(a: &[f64], b: &[f64]) -> Option<TTest> {
+    if a.len() < 2 || b.len() < 2 {
+        return None;
+    }
+
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    let (va, vb) = (std_dev(a).powi(2) / na, std_dev(b).powi(2) / nb);
+    let diff = mean(a) - mean(b);
+
+    if va + vb == 0.0 {
+        let p = if diff == 0.0 { 1.0 } else { 0.0 };
+        return Some(TTest {
+            t: diff.signum() * f64::INFINITY,
+            df: na + nb - 2.0,
+            p,
+        });
+    }
+
+    let t = diff / (va + vb).sqrt();
+    let df = (va + vb).powi(2) / (va.powi(2) / (na - 1.0) + vb.powi(2) / (nb - 1.0));
+    let p = incomplete_beta(df / 2.0, 0.5, df / (df + t * t));
+
+    Some(TTest { t, df, p })
+}
+
+/// Natural logarithm of the gamma function, using the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // reflection formula.
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + 1.0 + i as f64)
+        });
+
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Regularized incomplete beta function `I_x(a, b)`, evaluated with a continued fraction.
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    // the continued fraction converges quickly for `x < (a + 1) / (a + b + 2)`, use the symmetry otherwise.
+    if x > (a + 1.0) / (a + b + 2.0) {
+        return 1.0 - incomplete_beta(b, a, 1.0 - x);
+    }
+
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp() / a;
+
+    // modified Lentz's method.
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    d = if d.abs() < TINY { 1.0 / TINY } else { 1.0 / d };
+    let mut f = d;
+
+    for m in 1..200 {
+        let m = f64::from(m);
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            d = if d.abs() < TINY { 1.0 / TINY } else { 1.0 / d };
+            c = 1.0 + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            f *= c * d;
+        }
+
+        if (c * d - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+
+    front * f
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        incomplete_beta, ln_gamma, mean, median_abs_dev, quantile, std_dev, tukey_fences,
+        welch_t_test,
+    };
+
+    #[test]
+    fn computes_moments() {
+        let samples = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert!((mean(&samples) - 5.0).abs() < 1e-9);
+        assert!((std_dev(&samples) - 2.138_089_935).abs() < 1e-6);
+        assert!(mean(&[]).abs() < f64::EPSILON);
+        assert!(std_dev(&[1.0]).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn computes_quantiles() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert!((quantile(&sorted, 0.5) - 3.0).abs() < f64::EPSILON);
+        assert!((quantile(&sorted, 0.25) - 2.0).abs() < f64::EPSILON);
+        assert!((quantile(&sorted, 0.1) - 1.4).abs() < 1e-9);
+        assert_eq!(tukey_fences(&sorted), [-4.0, -1.0, 7.0, 10.0]);
+        assert!((median_abs_dev(&sorted) - 1.4826).abs() < 1e-9);
+    }
+
+    #[test]
+    fn computes_special_functions() {
+        // Γ(5) = 24, Γ(0.5) = √π
+        assert!((ln_gamma(5.0) - 24_f64.ln()).abs() < 1e-10);
+        assert!((ln_gamma(0.5) - std::f64::consts::PI.sqrt().ln()).abs() < 1e-10);
+        // I_x(1, 1) = x, I_0.5(a, a) = 0.5
+        assert!((incomplete_beta(1.0, 1.0, 0.3) - 0.3).abs() < 1e-10);
+        assert!((incomplete_beta(3.5, 3.5, 0.5) - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn runs_welch_t_test() {
+        let test = welch_t_test(&[1.0, 2.0, 3.0, 4.0, 5.0], &[2.0, 4.0, 6.0, 8.0, 10.0]).unwrap();
+        assert!((test.t + 1.897_366_596).abs() < 1e-6);
+        assert!((test.df - 5.882_352_941).abs() < 1e-6);
+        assert!((test.p - 0.107_531).abs() < 1e-4);
+
+        let test = welch_t_test(&[1.0, 1.0], &[1.0, 1.0]).unwrap();
+        assert!((test.p - 1.0).abs() < f64::EPSILON);
+        assert!(welch_t_test(&[1.0], &[1.0, 2.0]).is_none());
+    }
+}
@@ -0,0 +1,245 @@
+/// Module that submits answers on behalf of the `solve` command.
+/// Before an answer is sent, the example tests of its part have to pass and the submission has to be confirmed.
+use std::io::{self, BufRead, Write};
+use std::process::Command;
+
+use crate::template::answers::Answers;
+use crate::template::{aoc_cli, readme_stars, PuzzleId};
+
+/// How an answer is submitted.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SubmitOptions {
+    /// Part to submit. Without a part, the first part without an accepted answer is submitted.
+    pub part: Option<u8>,
+    /// Check the answer and the example tests, but do not submit.
+    pub dry_run: bool,
+    /// Submit without confirmation once the example tests pass.
+    pub auto: bool,
+}
+
+/// Outcome of the example tests of a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Examples {
+    Passed(usize),
+    Failed(usize),
+    Missing,
+}
+
+/// The part that is submitted: the requested one, or the first part of the puzzle without an accepted answer.
+/// Fails if the requested part does not exist, e.g. part two of the last day.
+pub fn part_to_submit(
+    options: &SubmitOptions,
+    puzzle: PuzzleId,
+    answers: &Answers,
+) -> Result<Option<u8>, String> {
+    match options.part {
+        Some(part) if !(1..=puzzle.part_count()).contains(&part) => {
+            Err(format!("{puzzle} does not have a part {part} to submit."))
+        }
+        Some(part) => Ok(Some(part)),
+        None => Ok((1..=puzzle.part_count()).find(|part| !answers.is_solved(puzzle.day, *part))),
+    }
+}
+
+/// Run the example tests of a part, i.e. the tests of the solution bin whose name contains `part_one` or `part_two`.
+pub fn run_examples(puzzle: PuzzleId, part: u8, is_release: bool) -> Result<Examples, io::Error> {
+    let filter = if part == 1 { "part_one" } else { "part_two" };
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["test", "--quiet", "--bin", &puzzle.bin_name()]);
+    if is_release {
+        cmd.arg("--release");
+    }
+    let output = cmd.args(["--", filter]).output()?;
+
+    match parse_test_results(&String::from_utf8_lossy(&output.stdout)) {
+        // e.g. a solution that does not compile, which is reported on stderr.
+        Examples::Passed(_) | Examples::Missing if !output.status.success() => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let lines: Vec<&str> = stderr.lines().collect();
+            Err(io::Error::other(format!(
+                "`cargo test` exited with {}:\n{}",
+                output.status,
+                lines[lines.len().saturating_sub(20)..].join("\n")
+            )))
+        }
+        examples => Ok(examples),
+    }
+}
+
+/// Sum up the `test result: …` lines of the output of `cargo test`.
+#[must_use]
+pub fn parse_test_results(output: &str) -> Examples {
+    let (mut passed, mut failed) = (0, 0);
+
+    for line in output.lines() {
+        let Some(summary) = line.split_once("test result: ").map(|(_, s)| s) else {
+            continue;
+        };
+
+        for field in summary.split([';', '.']) {
+            let count = |suffix: &str| {
+                field
+                    .trim()
+                    .strip_suffix(suffix)
+                    .and_then(|n| n.trim().parse::<usize>().ok())
+            };
+            if let Some(n) = count(" passed") {
+                passed += n;
+            } else if let Some(n) = count(" failed") {
+                failed += n;
+            }
+        }
+    }
+
+    if failed > 0 {
+        Examples::Failed(failed)
+    } else if passed > 0 {
+        Examples::Passed(passed)
+    } else {
+        Examples::Missing
+    }
+}
+
+/// Submit the answer of a part, after checking its examples and asking for confirmation.
+pub fn submit(
+    puzzle: PuzzleId,
+    part: u8,
+    answer: &str,
+    options: &SubmitOptions,
+    is_release: bool,
+) -> Result<(), String> {
+    println!("Running the example tests of part {part}...");
+    let examples = run_examples(puzzle, part, is_release)
+        .map_err(|e| format!("Refusing to submit, the example tests could not be run: {e}"))?;
+
+    match examples {
+        Examples::Failed(count) => {
+            return Err(format!(
+                "Refusing to submit, {count} example tests of part {part} failed. Check them with `cargo test --bin {}`.",
+                puzzle.bin_name()
+            ))
+        }
+        Examples::Passed(count) => println!("{count} example tests of part {part} passed."),
+        Examples::Missing => println!("There are no example tests for part {part}."),
+    }
+
+    if options.dry_run {
+        println!("Dry run, not submitting `{answer}` for {puzzle} part {part}.");
+        return Ok(());
+    }
+
+    let is_confirmed = (options.auto && matches!(examples, Examples::Passed(_)))
+        || confirm(&format!("Submit `{answer}` for {puzzle} part {part}?"));
+
+    if !is_confirmed {
+        println!("Not submitted.");
+        return Ok(());
+    }
+
+    aoc_cli::check().map_err(|_| {
+        "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            .to_string()
+    })?;
+
+    println!("Submitting result via aoc-cli...");
+    let output = aoc_cli::submit(puzzle, part, answer).map_err(|e| e.to_string())?;
+
+    if aoc_cli::is_correct_answer(&output) {
+        record_answer(puzzle, part, answer);
+    }
+
+    Ok(())
+}
+
+/// Ask a yes/no question on the terminal, defaulting to no.
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+
+    let mut reply = String::new();
+    if io::stdin().lock().read_line(&mut reply).is_err() {
+        return false;
+    }
+    matches!(reply.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Store an accepted answer and update the stars table in the readme.
+fn record_answer(puzzle: PuzzleId, part: u8, answer: &str) {
+    let mut answers = match Answers::read_from_file(puzzle.year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("The accepted answer `{answer}` for part {part} was not recorded.");
+            return;
+        }
+    };
+    answers.record(puzzle.day, part, answer);
+
+    if let Err(e) = answers.store_file(puzzle.year) {
+        eprintln!("Failed to record accepted answer: {e}");
+        return;
+    }
+
+    let stars = readme_stars::from_answers(&answers, puzzle.year);
+    if let Err(e) = readme_stars::update(puzzle.year, &stars) {
+        eprintln!("Failed to update stars table: {e}");
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_test_results, part_to_submit, Examples, SubmitOptions};
+    use crate::day;
+    use crate::template::answers::Answers;
+    use crate::template::{PuzzleId, Year};
+
+    #[test]
+    fn parses_test_results() {
+        let output = "\nrunning 2 tests\n..\ntest result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 1 filtered out; finished in 0.00s\n";
+        assert_eq!(parse_test_results(output), Examples::Passed(2));
+
+        let output =
+            "test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out";
+        assert_eq!(parse_test_results(output), Examples::Failed(1));
+
+        let output = "test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 3 filtered out";
+        assert_eq!(parse_test_results(output), Examples::Missing);
+    }
+
+    #[test]
+    fn picks_next_unsolved_part() {
+        let puzzle = PuzzleId::new(Year::new(2023).unwrap(), day!(5));
+        let mut answers = Answers::default();
+        let options = SubmitOptions::default();
+
+        assert_eq!(part_to_submit(&options, puzzle, &answers), Ok(Some(1)));
+        answers.record(day!(5), 1, "42");
+        assert_eq!(part_to_submit(&options, puzzle, &answers), Ok(Some(2)));
+        answers.record(day!(5), 2, "7");
+        assert_eq!(part_to_submit(&options, puzzle, &answers), Ok(None));
+
+        let options = SubmitOptions {
+            part: Some(1),
+            ..SubmitOptions::default()
+        };
+        assert_eq!(part_to_submit(&options, puzzle, &answers), Ok(Some(1)));
+    }
+
+    #[test]
+    fn rejects_missing_parts() {
+        let answers = Answers::default();
+        let part = |part| SubmitOptions {
+            part: Some(part),
+            ..SubmitOptions::default()
+        };
+
+        let puzzle = PuzzleId::new(Year::new(2023).unwrap(), day!(5));
+        assert!(part_to_submit(&part(3), puzzle, &answers).is_err());
+        assert!(part_to_submit(&part(0), puzzle, &answers).is_err());
+
+        let last_day = PuzzleId::new(Year::new(2023).unwrap(), day!(25));
+        assert!(part_to_submit(&part(2), last_day, &answers).is_err());
+        assert_eq!(part_to_submit(&part(1), last_day, &answers), Ok(Some(1)));
+    }
+}
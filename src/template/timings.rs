@@ -1,17 +1,185 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io, path::PathBuf, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::alloc::AllocStats;
+use crate::template::host;
+use crate::template::{Day, PuzzleId, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the schema of timing files.
+/// Files without a version were written before the schema was versioned and are migrated when read:
+/// - 1: run times of parts as formatted strings, e.g. `"part_1": "1.2ms"`.
+/// - 2: run times of parts in nanoseconds, e.g. `"part_1_nanos": 1200000`, and metadata of the measurement.
+pub const SCHEMA_VERSION: u64 = 2;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(s) => f.write_str(s),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Path of the timings file of a year, e.g. `data/timings.json` for the primary year.
+/// Named benchmark tables store their timings separately, e.g. in `data/timings.laptop.json`.
+#[must_use]
+pub fn get_path(year: Year, table: Option<&str>) -> PathBuf {
+    match table {
+        Some(name) => year.data_dir().join(format!("timings.{name}.json")),
+        None => year.data_dir().join(TIMINGS_FILE_NAME),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parse a duration in the debug format of [`std::time::Duration`] into nanoseconds.
+pub(crate) fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Parse a size as formatted by the runner, e.g. `4.0KiB`, into bytes.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn parse_bytes(s: &str) -> Option<u64> {
+    let units = [
+        ("GiB", 1 << 30),
+        ("MiB", 1 << 20),
+        ("KiB", 1 << 10),
+        ("B", 1),
+    ];
+    let (value, factor) = units
+        .iter()
+        .find_map(|(unit, factor)| Some((s.strip_suffix(unit)?, *factor)))?;
+    value
+        .parse::<f64>()
+        .ok()
+        .map(|x| (x * f64::from(factor)).round() as u64)
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
     pub total_nanos: f64,
+    /// Whether the solution was killed because it exceeded its timeout.
+    pub timed_out: bool,
+    pub stats: TimingStats,
+    /// How and when the timing was measured. Unknown for timings migrated from version 1.
+    pub meta: Option<TimingMeta>,
+}
+
+/// Describes how and when a timing was measured.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingMeta {
+    /// Cargo profile the solution was built with, e.g. `release`.
+    pub profile: String,
+    /// Output of `rustc --version`.
+    pub rustc: Option<String>,
+    /// Model name of the CPU the solution ran on.
+    pub cpu: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl TimingMeta {
+    /// Describe a measurement that is taken now on this machine.
+    pub fn detect(is_release: bool) -> Self {
+        TimingMeta {
+            profile: if is_release { "release" } else { "dev" }.into(),
+            rustc: host::rustc_version(),
+            cpu: host::cpu(),
+            timestamp: host::now(),
+        }
+    }
+}
+
+/// Statistics of the benchmark samples of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartStats {
+    pub samples: u64,
+    pub median_nanos: f64,
+    pub stddev_nanos: f64,
+}
+
+/// Resources used by the process that ran the solutions of a day, as reported by the OS.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourceUsage {
+    pub user_nanos: f64,
+    pub system_nanos: f64,
+    /// Run time of the process, including startup and input reading.
+    pub wall_nanos: f64,
+    /// Largest resident set size of the process.
+    pub max_rss_bytes: u64,
+}
+
+impl ResourceUsage {
+    /// CPU time spent in both user and kernel mode.
+    #[must_use]
+    pub fn cpu_nanos(&self) -> f64 {
+        self.user_nanos + self.system_nanos
+    }
+
+    /// Ratio of CPU time to wall time, e.g. above one for solutions that use multiple threads.
+    #[must_use]
+    pub fn parallelism(&self) -> f64 {
+        if self.wall_nanos > 0.0 {
+            self.cpu_nanos() / self.wall_nanos
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Additional measurements of a day.
+/// Every value is optional, since they are not recorded by every run and older timing files lack them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TimingStats {
+    pub part_1: Option<PartStats>,
+    pub part_2: Option<PartStats>,
+    /// Heap allocations of a single run of each part, recorded by `cargo time --alloc`.
+    pub part_1_alloc: Option<AllocStats>,
+    pub part_2_alloc: Option<AllocStats>,
+    /// Run time of the shared parse function, not included in the total.
+    pub parse_nanos: Option<f64>,
+    /// Largest heap usage of the parts of a day.
+    pub peak_memory_bytes: Option<u64>,
+    /// Number of heap allocations of all parts of a day.
+    pub allocations: Option<u64>,
+    /// CPU time and max RSS of the solution process. Only recorded on unix platforms.
+    pub usage: Option<ResourceUsage>,
+}
+
+impl Timing {
+    /// Run time of a part in nanoseconds, if it was measured.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        match part {
+            1 => self.part_1_nanos,
+            _ => self.part_2_nanos,
+        }
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,34 +190,49 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings of a year to a JSON file.
+    pub fn store_file(&self, year: Year, table: Option<&str>) -> Result<(), io::Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let path = get_path(year, table);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
-
-        match s {
-            Ok(timings) => timings,
-            Err(e) => {
-                eprintln!("{e}");
-                Timings::default()
-            }
+    /// Rehydrate timings of a year from a JSON file. If not present, returns empty timings.
+    /// Files of older schema versions are migrated, files that can not be read result in an error.
+    pub fn read_from_file(year: Year, table: Option<&str>) -> Result<Self, Error> {
+        let path = get_path(year, table);
+
+        if !path.exists() {
+            return Ok(Timings::default());
         }
+
+        let s = fs::read_to_string(&path)?;
+        Timings::try_from(s).map_err(|e| {
+            Error::Parser(format!(
+                "Could not read timings from `{}`: {e}",
+                path.display()
+            ))
+        })
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// A timing of `new` that timed out does not replace a stored timing that completed.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let keeps_stored = timing.timed_out
+                && self
+                    .data
+                    .iter()
+                    .any(|t| t.day == timing.day && !t.timed_out);
+            if !keeps_stored {
+                data.push(timing.clone());
+            }
         }
 
         for timing in &self.data {
@@ -58,7 +241,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -67,10 +250,13 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+    /// Whether there are timings for every part of a puzzle.
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
+        self.data.iter().any(|t| {
+            t.day == puzzle.day
+                && t.part_1_nanos.is_some()
+                && (puzzle.part_count() < 2 || t.part_2_nanos.is_some())
+        })
     }
 }
 
@@ -80,6 +266,8 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("version".into(), JsonValue::Number(SCHEMA_VERSION as f64));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -95,9 +283,26 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let version = match json.get("version") {
+            Some(v) => v
+                .get::<f64>()
+                .map(|v| *v as u64)
+                .ok_or("expected `json.version` to be a number.")?,
+            None => 1,
+        };
+
+        if version > SCHEMA_VERSION {
+            return Err(format!(
+                "schema version {version} is newer than the supported version {SCHEMA_VERSION}, please update the template."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -106,7 +311,10 @@ impl TryFrom<String> for Timings {
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|v| match version {
+                    1 => Timing::try_from(&migrate_v1(v)?),
+                    _ => Timing::try_from(v),
+                })
                 .collect::<Result<_, _>>()?,
         })
     }
@@ -114,30 +322,40 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
+/// Convert a timing of schema version 1 to the current schema.
+fn migrate_v1(value: &JsonValue) -> Result<JsonValue, String> {
+    let mut json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing to be a JSON object.")?
+        .clone();
+
+    for key in ["part_1", "part_2"] {
+        let nanos = match json.remove(key) {
+            Some(JsonValue::String(s)) => JsonValue::Number(
+                parse_duration(&s).ok_or(format!("Expected timing.{key} to be a duration."))?,
+            ),
+            Some(JsonValue::Null) => JsonValue::Null,
+            _ => return Err(format!("Expected timing.{key} to be null or string.")),
+        };
+        json.insert(format!("{key}_nanos"), nanos);
+    }
+
+    Ok(JsonValue::Object(json))
+}
+
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part_1_nanos".into(), optional_number(value.part_1_nanos));
+        map.insert("part_2_nanos".into(), optional_number(value.part_2_nanos));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
+        map.insert("timed_out".into(), JsonValue::Boolean(value.timed_out));
+        map.insert("stats".into(), JsonValue::from(&value.stats));
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
-
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "meta".into(),
+            value.meta.as_ref().map_or(JsonValue::Null, JsonValue::from),
         );
 
         JsonValue::Object(map)
@@ -158,26 +376,269 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        let part_nanos = |key: &str| match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::Number(nanos)) => Ok(Some(*nanos)),
+            _ => Err(format!("Expected timing.{key} to be null or a number.")),
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let part_1_nanos = part_nanos("part_1_nanos")?;
+        let part_2_nanos = part_nanos("part_2_nanos")?;
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // timing files of version 1 do not always record timeouts.
+        let timed_out = match json.get("timed_out") {
+            Some(v) => *v
+                .get::<bool>()
+                .ok_or("Expected timing.timed_out to be a boolean.")?,
+            None => false,
+        };
+
+        // timing files of version 1 do not always record stats.
+        let stats = match json.get("stats") {
+            Some(v) => TimingStats::try_from(v)?,
+            None => TimingStats::default(),
+        };
+
+        let meta = match json.get("meta") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(TimingMeta::try_from(v)?),
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1_nanos,
+            part_2_nanos,
             total_nanos,
+            timed_out,
+            stats,
+            meta,
+        })
+    }
+}
+
+impl From<&TimingMeta> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &TimingMeta) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let optional_string =
+            |s: &Option<String>| s.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("rustc".into(), optional_string(&value.rustc));
+        map.insert("cpu".into(), optional_string(&value.cpu));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for TimingMeta {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing.meta to be a JSON object.")?;
+
+        let optional_string = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            _ => Err(format!(
+                "Expected timing.meta.{key} to be null or a string."
+            )),
+        };
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected timing.meta.profile to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|t| *t as u64)
+            .ok_or("Expected timing.meta.timestamp to be a number.")?;
+
+        Ok(TimingMeta {
+            profile: profile.clone(),
+            rustc: optional_string("rustc")?,
+            cpu: optional_string("cpu")?,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_number(value: Option<f64>) -> JsonValue {
+    value.map_or(JsonValue::Null, JsonValue::Number)
+}
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&TimingStats> for JsonValue {
+    fn from(value: &TimingStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            let json = part.as_ref().map_or(JsonValue::Null, |stats| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("samples".into(), JsonValue::Number(stats.samples as f64));
+                map.insert("median_nanos".into(), JsonValue::Number(stats.median_nanos));
+                map.insert("stddev_nanos".into(), JsonValue::Number(stats.stddev_nanos));
+                JsonValue::Object(map)
+            });
+            map.insert(key.into(), json);
+        }
+
+        for (key, alloc) in [
+            ("part_1_alloc", &value.part_1_alloc),
+            ("part_2_alloc", &value.part_2_alloc),
+        ] {
+            let json = alloc.as_ref().map_or(JsonValue::Null, |alloc| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert(
+                    "allocations".into(),
+                    JsonValue::Number(alloc.allocations as f64),
+                );
+                map.insert("bytes".into(), JsonValue::Number(alloc.bytes as f64));
+                map.insert(
+                    "peak_bytes".into(),
+                    JsonValue::Number(alloc.peak_bytes as f64),
+                );
+                JsonValue::Object(map)
+            });
+            map.insert(key.into(), json);
+        }
+
+        map.insert("parse_nanos".into(), optional_number(value.parse_nanos));
+        map.insert(
+            "peak_memory_bytes".into(),
+            optional_number(value.peak_memory_bytes.map(|x| x as f64)),
+        );
+        map.insert(
+            "allocations".into(),
+            optional_number(value.allocations.map(|x| x as f64)),
+        );
+
+        let usage = value.usage.as_ref().map_or(JsonValue::Null, |usage| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("user_nanos".into(), JsonValue::Number(usage.user_nanos));
+            map.insert("system_nanos".into(), JsonValue::Number(usage.system_nanos));
+            map.insert("wall_nanos".into(), JsonValue::Number(usage.wall_nanos));
+            map.insert(
+                "max_rss_bytes".into(),
+                JsonValue::Number(usage.max_rss_bytes as f64),
+            );
+            JsonValue::Object(map)
+        });
+        map.insert("usage".into(), usage);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for TimingStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing.stats to be a JSON object.")?;
+
+        let number = |key: &str| -> Result<Option<f64>, String> {
+            match json.get(key) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(v) => v.get::<f64>().copied().map(Some).ok_or(format!(
+                    "Expected timing.stats.{key} to be null or a number."
+                )),
+            }
+        };
+
+        let part = |key: &str| -> Result<Option<PartStats>, String> {
+            let Some(v) = json.get(key).filter(|v| !v.is_null()) else {
+                return Ok(None);
+            };
+
+            let err = || format!("Expected timing.stats.{key} to be null or part stats.");
+            let stats = v.get::<HashMap<String, JsonValue>>().ok_or_else(err)?;
+            let field = |name: &str| {
+                stats
+                    .get(name)
+                    .and_then(|v| v.get::<f64>().copied())
+                    .ok_or_else(err)
+            };
+
+            Ok(Some(PartStats {
+                samples: field("samples")? as u64,
+                median_nanos: field("median_nanos")?,
+                stddev_nanos: field("stddev_nanos")?,
+            }))
+        };
+
+        let alloc = |key: &str| -> Result<Option<AllocStats>, String> {
+            let Some(v) = json.get(key).filter(|v| !v.is_null()) else {
+                return Ok(None);
+            };
+
+            let err = || format!("Expected timing.stats.{key} to be null or allocation stats.");
+            let stats = v.get::<HashMap<String, JsonValue>>().ok_or_else(err)?;
+            let field = |name: &str| {
+                stats
+                    .get(name)
+                    .and_then(|v| v.get::<f64>().copied())
+                    .map(|x| x as u64)
+                    .ok_or_else(err)
+            };
+
+            Ok(Some(AllocStats {
+                allocations: field("allocations")?,
+                bytes: field("bytes")?,
+                peak_bytes: field("peak_bytes")?,
+            }))
+        };
+
+        let usage = match json.get("usage").filter(|v| !v.is_null()) {
+            None => None,
+            Some(v) => {
+                let err =
+                    || "Expected timing.stats.usage to be null or resource usage.".to_string();
+                let usage = v.get::<HashMap<String, JsonValue>>().ok_or_else(err)?;
+                let field = |name: &str| {
+                    usage
+                        .get(name)
+                        .and_then(|v| v.get::<f64>().copied())
+                        .ok_or_else(err)
+                };
+
+                Some(ResourceUsage {
+                    user_nanos: field("user_nanos")?,
+                    system_nanos: field("system_nanos")?,
+                    wall_nanos: field("wall_nanos")?,
+                    max_rss_bytes: field("max_rss_bytes")? as u64,
+                })
+            }
+        };
+
+        Ok(TimingStats {
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+            part_1_alloc: alloc("part_1_alloc")?,
+            part_2_alloc: alloc("part_2_alloc")?,
+            parse_nanos: number("parse_nanos")?,
+            peak_memory_bytes: number("peak_memory_bytes")?.map(|x| x as u64),
+            allocations: number("allocations")?.map(|x| x as u64),
+            usage,
         })
     }
 }
@@ -188,28 +649,37 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{Timing, TimingStats, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1_nanos: Some(10e6),
+                    part_2_nanos: Some(20e6),
                     total_nanos: 3e+10,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1_nanos: Some(30e6),
+                    part_2_nanos: Some(40e6),
                     total_nanos: 7e+10,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
+                    part_1_nanos: Some(40e6),
+                    part_2_nanos: None,
                     total_nanos: 4e+10,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 },
             ],
         }
@@ -225,9 +695,45 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
-            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.part_1_nanos, Some(1_000_000_f64));
+            assert_eq!(timing.part_2_nanos, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.meta, None);
+        }
+
+        #[test]
+        fn handles_versioned_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1_nanos": 1200, "part_2_nanos": null, "total_nanos": 1200, "timed_out": false, "stats": {}, "meta": { "profile": "release", "rustc": "rustc 1.83.0", "cpu": null, "timestamp": 1701388800 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_nanos, Some(1200_f64));
+            assert_eq!(timing.part_2_nanos, None);
+
+            let meta = timing.meta.as_ref().unwrap();
+            assert_eq!(meta.profile, "release");
+            assert_eq!(meta.rustc.as_deref(), Some("rustc 1.83.0"));
+            assert_eq!(meta.cpu, None);
+            assert_eq!(meta.timestamp, 1_701_388_800);
+        }
+
+        #[test]
+        fn rejects_newer_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            let err = Timings::try_from(json).unwrap_err();
+            assert!(err.contains("schema version 3"));
+        }
+
+        #[test]
+        fn rejects_malformed_v1_durations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "fast", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
+        #[test]
+        fn handles_timed_out_timings() {
+            let json = r#"{ "data": [{ "day": "05", "part_1": null, "part_2": null, "total_nanos": 0, "timed_out": true }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.first().unwrap().timed_out, true);
         }
 
         #[test]
@@ -254,6 +760,7 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{ResourceUsage, TimingMeta, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -273,12 +780,41 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[0].meta = Some(TimingMeta {
+                profile: "release".into(),
+                rustc: Some("rustc 1.83.0".into()),
+                cpu: None,
+                timestamp: 1_701_388_800,
+            });
+            timings.data[1].stats.usage = Some(ResourceUsage {
+                user_nanos: 1.5e9,
+                system_nanos: 0.5e9,
+                wall_nanos: 1e9,
+                max_rss_bytes: 12_582_912,
+            });
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert!(json.contains(r#""version":2"#));
+
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[0].meta, timings.data[0].meta);
+            assert_eq!(parsed.data[0].stats.usage, None);
+            assert_eq!(parsed.data[1].stats.usage, timings.data[1].stats.usage);
+            assert_eq!(parsed.data[2].part_1_nanos, Some(40e6));
+            assert_eq!(parsed.data[2].part_2_nanos, None);
+        }
     }
 
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{Timing, TimingStats, Timings},
+            template::{PuzzleId, Year},
         };
 
         #[test]
@@ -286,13 +822,16 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1_nanos: Some(1e6),
+                    part_2_nanos: Some(2e6),
                     total_nanos: 3_000_000_000_f64,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(PuzzleId::primary(day!(1))), true);
         }
 
         #[test]
@@ -300,13 +839,16 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
+                    part_1_nanos: Some(1e6),
+                    part_2_nanos: None,
                     total_nanos: 1_000_000_000_f64,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(PuzzleId::primary(day!(1))), false);
         }
 
         #[test]
@@ -314,20 +856,42 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: None,
-                    part_2: None,
+                    part_1_nanos: None,
+                    part_2_nanos: None,
                     total_nanos: 0.0,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(PuzzleId::primary(day!(1))), false);
+        }
+
+        #[test]
+        fn handles_single_part_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(12),
+                    part_1_nanos: Some(1e6),
+                    part_2_nanos: None,
+                    total_nanos: 1_000_000_000_f64,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            let year = Year::new(2025).unwrap();
+            assert_eq!(timings.is_day_complete(PuzzleId::new(year, day!(12))), true);
+            assert_eq!(timings.is_day_complete(PuzzleId::primary(day!(12))), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{Timing, TimingStats, Timings},
         };
 
         use super::get_mock_timings;
@@ -338,9 +902,12 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
-                    part_1: None,
-                    part_2: None,
+                    part_1_nanos: None,
+                    part_2_nanos: None,
                     total_nanos: 0_f64,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -358,9 +925,12 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
-                    part_1: None,
-                    part_2: None,
+                    part_1_nanos: None,
+                    part_2_nanos: None,
                     total_nanos: 0_f64,
+                    timed_out: false,
+                    stats: TimingStats::default(),
+                    meta: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -372,6 +942,31 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_stored_timings_over_timeouts() {
+            let timings = get_mock_timings();
+            let timed_out = |day| Timing {
+                day,
+                part_1_nanos: None,
+                part_2_nanos: None,
+                total_nanos: 0_f64,
+                timed_out: true,
+                stats: TimingStats::default(),
+                meta: None,
+            };
+
+            let other = Timings {
+                data: vec![timed_out(day!(2)), timed_out(day!(3))],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 4);
+            assert!(!merged.data[1].timed_out);
+            assert_eq!(merged.data[1].total_nanos, 7e+10);
+            // days without a stored timing still record the timeout.
+            assert!(merged.data[2].timed_out);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
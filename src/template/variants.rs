@@ -0,0 +1,169 @@
+/// Helpers to run and compare the named variants of a solution.
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::template::readme_benchmarks::format_nanos;
+use crate::template::run_multi::{child_commands, Error};
+use crate::template::timings::Timing;
+use crate::template::{all_days, Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Answers of one variant for a single input.
+pub struct VariantAnswers {
+    pub variant: String,
+    pub answers: Vec<(u8, String)>,
+}
+
+/// A part for which variants produced different answers on the same input.
+pub struct Disagreement {
+    pub part: u8,
+    pub answers: Vec<(String, String)>,
+}
+
+/// Run every variant against an input file and collect their answers.
+pub fn run_all(
+    puzzle: PuzzleId,
+    variants: &[String],
+    input_path: &Path,
+    is_release: bool,
+) -> Result<Vec<VariantAnswers>, Error> {
+    let input = input_path.to_string_lossy();
+
+    variants
+        .iter()
+        .map(|variant| {
+            let output = child_commands::run_bin(
+                puzzle,
+                is_release,
+                &["--variant", variant, "--input", &input],
+            )?;
+
+            Ok(VariantAnswers {
+                variant: variant.clone(),
+                answers: child_commands::parse_answers(&output),
+            })
+        })
+        .collect()
+}
+
+/// Find the parts where not all variants that implement them agree on the answer.
+pub fn find_disagreements(results: &[VariantAnswers]) -> Vec<Disagreement> {
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let answers: Vec<(String, String)> = results
+                .iter()
+                .filter_map(|r| {
+                    r.answers
+                        .iter()
+                        .find(|(p, _)| *p == part)
+                        .map(|(_, answer)| (r.variant.clone(), answer.clone()))
+                })
+                .collect();
+
+            let distinct: HashSet<&String> = answers.iter().map(|(_, answer)| answer).collect();
+
+            if distinct.len() > 1 {
+                Some(Disagreement { part, answers })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Bench every variant of the given days and print their timings side by side.
+pub fn bench(days_to_run: &HashSet<Day>, year: Year) -> Result<(), Error> {
+    let puzzles: Vec<PuzzleId> = all_days()
+        .filter(|d| days_to_run.contains(d))
+        .map(|d| PuzzleId::new(year, d))
+        .collect();
+    child_commands::build_solutions(&puzzles, true)?;
+
+    for puzzle in puzzles {
+        let variants = child_commands::list_variants(puzzle, true)?;
+
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+        println!("------");
+
+        let mut rows: Vec<(String, Timing)> = vec![];
+
+        for variant in variants {
+            println!("Benching variant `{variant}`...");
+            let output = child_commands::run_bin(puzzle, true, &["--variant", &variant, "--time"])?;
+            rows.push((
+                variant,
+                child_commands::parse_exec_time(&output, puzzle.day),
+            ));
+        }
+
+        println!();
+        println!("{}", format_table(&rows));
+        println!();
+    }
+
+    Ok(())
+}
+
+fn format_table(rows: &[(String, Timing)]) -> String {
+    let width = rows
+        .iter()
+        .map(|(variant, _)| variant.len())
+        .max()
+        .unwrap_or(0)
+        .max("Variant".len());
+
+    let mut lines = vec![
+        format!(
+            "| {:width$} | {:>12} | {:>12} |",
+            "Variant", "Part 1", "Part 2"
+        ),
+        format!("| {:-<width$} | {:->12} | {:->12} |", "", "", ""),
+    ];
+
+    for (variant, timing) in rows {
+        lines.push(format!(
+            "| {:width$} | {:>12} | {:>12} |",
+            variant,
+            timing.part_1_nanos.map_or_else(|| "-".into(), format_nanos),
+            timing.part_2_nanos.map_or_else(|| "-".into(), format_nanos),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_disagreements, VariantAnswers};
+
+    fn answers(variant: &str, answers: &[(u8, &str)]) -> VariantAnswers {
+        VariantAnswers {
+            variant: variant.into(),
+            answers: answers
+                .iter()
+                .map(|(p, a)| (*p, (*a).to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn finds_no_disagreements_for_equal_answers() {
+        let results = [
+            answers("default", &[(1, "21"), (2, "525152")]),
+            answers("naive", &[(1, "21")]),
+        ];
+        assert!(find_disagreements(&results).is_empty());
+    }
+
+    #[test]
+    fn finds_disagreements_per_part() {
+        let results = [
+            answers("default", &[(1, "21"), (2, "525152")]),
+            answers("naive", &[(1, "22"), (2, "525152")]),
+        ];
+        let disagreements = find_disagreements(&results);
+        assert_eq!(disagreements.len(), 1);
+        assert_eq!(disagreements[0].part, 1);
+        assert_eq!(disagreements[0].answers.len(), 2);
+    }
+}
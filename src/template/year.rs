@@ -0,0 +1,404 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{config, AllDays, Day};
+
+/// The first year of advent of code.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first year with a shortened calendar of 12 days.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// A valid year of advent of code (i.e. 2015 or later).
+///
+/// One year of the workspace is the _primary_ year (see [`Year::primary`]). Its puzzles live in the flat
+/// `data/inputs/DD.txt` and `src/bin/DD.rs` layout, while other years are stored under `data/YYYY/` and
+/// `src/bin/YYYY-DD.rs`.
+///
+/// # Display
+/// This value displays as a four digit number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The primary year of the workspace, read from `year` in `aoc.toml` or the `AOC_YEAR` environment variable.
+    /// Falls back to the current year if neither is set.
+    #[must_use]
+    pub fn primary() -> Self {
+        config::get().year.unwrap_or_else(Self::current)
+    }
+
+    /// The current year in UTC.
+    #[must_use]
+    pub fn current() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        #[allow(clippy::cast_possible_wrap)]
+        let (year, _, _) = civil_from_days((secs / 86_400) as i64);
+        Self(u16::try_from(year).unwrap_or(u16::MAX).max(FIRST_YEAR))
+    }
+
+    /// Number of days in the advent calendar of this year: 25 until 2024, 12 since 2025.
+    #[must_use]
+    pub fn day_count(self) -> u8 {
+        if self.0 >= FIRST_SHORT_YEAR {
+            12
+        } else {
+            25
+        }
+    }
+
+    /// The last day of the advent calendar of this year.
+    #[must_use]
+    pub fn last_day(self) -> Day {
+        Day::__new_unchecked(self.day_count())
+    }
+
+    /// Whether a day is part of the advent calendar of this year.
+    #[must_use]
+    pub fn has_day(self, day: Day) -> bool {
+        day <= self.last_day()
+    }
+
+    /// An iterator over every day of the advent calendar of this year.
+    #[must_use]
+    pub fn days(self) -> AllDays {
+        AllDays::until(self.last_day())
+    }
+
+    /// Whether this is the primary year of the workspace.
+    #[must_use]
+    pub fn is_primary(self) -> bool {
+        self == Self::primary()
+    }
+
+    /// Directory that holds the data files (inputs, examples, puzzles and timings) of this year, see `data_dir`
+    /// in `aoc.toml`.
+    #[must_use]
+    pub fn data_dir(self) -> PathBuf {
+        let data_dir = &config::get().data_dir;
+        if self.is_primary() {
+            data_dir.clone()
+        } else {
+            data_dir.join(self.to_string())
+        }
+    }
+}
+
+/// Converts days since the unix epoch into a `(year, month, day)` triple.
+/// See: <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts a `(year, month, day)` triple into days since the unix epoch, the inverse of [`civil_from_days`].
+/// See: <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a single puzzle by its year and day.
+///
+/// All paths of a puzzle are derived from this value:
+///
+/// | | primary year | other years |
+/// | --- | --- | --- |
+/// | solution | `src/bin/DD.rs` | `src/bin/YYYY-DD.rs` |
+/// | input | `data/inputs/DD.txt` | `data/YYYY/inputs/DD.txt` |
+/// | example | `data/examples/DD.txt` | `data/YYYY/examples/DD.txt` |
+/// | puzzle | `data/puzzles/DD.md` | `data/YYYY/puzzles/DD.md` |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// A puzzle of the primary year.
+    #[must_use]
+    pub fn primary(day: Day) -> Self {
+        Self::new(Year::primary(), day)
+    }
+
+    /// Name of the solution bin of this puzzle.
+    #[must_use]
+    pub fn bin_name(self) -> String {
+        if self.year.is_primary() {
+            self.day.to_string()
+        } else {
+            format!("{}-{}", self.year, self.day)
+        }
+    }
+
+    /// Inverse of [`PuzzleId::bin_name`], e.g. for the file stems of `src/bin`.
+    #[must_use]
+    pub fn from_bin_name(name: &str) -> Option<Self> {
+        match name.split_once('-') {
+            Some((year, day)) => Some(Self::new(year.parse().ok()?, day.parse().ok()?)),
+            None => Some(Self::primary(name.parse().ok()?)),
+        }
+    }
+
+    /// Number of parts of this puzzle. The puzzle of the last day of a year only has a single part,
+    /// its second star is awarded for completing every other puzzle.
+    #[must_use]
+    pub fn part_count(self) -> u8 {
+        if self.day == self.year.last_day() {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Path of the solution module of this puzzle, relative to the workspace root.
+    #[must_use]
+    pub fn bin_path(self) -> String {
+        format!("./src/bin/{}.rs", self.bin_name())
+    }
+
+    /// Path of a data file of this puzzle, e.g. `data/inputs/01.txt` for the folder `inputs` and extension `txt`.
+    #[must_use]
+    pub fn data_path(self, folder: &str, extension: &str) -> PathBuf {
+        self.year
+            .data_dir()
+            .join(folder)
+            .join(format!("{}.{extension}", self.day))
+    }
+
+    /// Path of the puzzle input.
+    #[must_use]
+    pub fn input_path(self) -> PathBuf {
+        self.data_path("inputs", "txt")
+    }
+
+    /// Path of the example input.
+    #[must_use]
+    pub fn example_path(self) -> PathBuf {
+        self.data_path("examples", "txt")
+    }
+
+    /// Path of the puzzle description.
+    #[must_use]
+    pub fn puzzle_path(self) -> PathBuf {
+        self.data_path("puzzles", "md")
+    }
+
+    /// Time at which this puzzle unlocks: midnight of its day in december, in UTC-5.
+    #[must_use]
+    pub fn unlock_time(self) -> SystemTime {
+        let days = days_from_civil(
+            i64::from(self.year.into_inner()),
+            12,
+            u32::from(self.day.into_inner()),
+        );
+        #[allow(clippy::cast_sign_loss)]
+        let secs = (days * 86_400 + 5 * 3_600) as u64;
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    /// The first puzzle that unlocks after `now`.
+    #[must_use]
+    pub fn next_unlock(now: SystemTime) -> Self {
+        let secs = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        // the calendar year of the puzzle site, in UTC-5.
+        #[allow(clippy::cast_possible_wrap)]
+        let (year, _, _) = civil_from_days((secs.saturating_sub(5 * 3_600) / 86_400) as i64);
+        let year = Year(u16::try_from(year).unwrap_or(u16::MAX).max(FIRST_YEAR));
+
+        year.days()
+            .map(|day| Self::new(year, day))
+            .find(|puzzle| puzzle.unlock_time() > now)
+            .unwrap_or_else(|| Self::new(Year(year.0 + 1), Day::__new_unchecked(1)))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{civil_from_days, days_from_civil, PuzzleId, Year};
+    use crate::day;
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_692), (2023, 12, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2023, 12, 1), 19_692);
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2023-12-01T05:00:00Z
+        let puzzle = PuzzleId::new(Year(2023), day!(1));
+        assert_eq!(
+            puzzle.unlock_time(),
+            UNIX_EPOCH + Duration::from_secs(1_701_406_800)
+        );
+
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        // 2023-06-01T00:00:00Z, before december.
+        assert_eq!(PuzzleId::next_unlock(at(1_685_577_600)), puzzle);
+        // one second before and at the unlock of the first day.
+        assert_eq!(PuzzleId::next_unlock(at(1_701_406_799)), puzzle);
+        assert_eq!(
+            PuzzleId::next_unlock(at(1_701_406_800)),
+            PuzzleId::new(Year(2023), day!(2))
+        );
+        // 2024-01-01T04:00:00Z is still december 31st in UTC-5.
+        assert_eq!(
+            PuzzleId::next_unlock(at(1_704_081_600)),
+            PuzzleId::new(Year(2024), day!(1))
+        );
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2022".parse::<Year>().unwrap(), Year(2022));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("abc".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn models_calendar_length() {
+        let year = Year(2023);
+        assert_eq!(year.days().count(), 25);
+        assert!(year.has_day(day!(25)));
+        assert_eq!(PuzzleId::new(year, day!(24)).part_count(), 2);
+        assert_eq!(PuzzleId::new(year, day!(25)).part_count(), 1);
+
+        let year = Year(2025);
+        assert_eq!(year.days().count(), 12);
+        assert_eq!(year.last_day(), day!(12));
+        assert!(!year.has_day(day!(13)));
+        assert_eq!(PuzzleId::new(year, day!(12)).part_count(), 1);
+    }
+
+    #[test]
+    fn builds_paths_for_other_years() {
+        // the year 2015 is never the primary year of this workspace.
+        let puzzle = PuzzleId::new(Year(2015), day!(3));
+        assert_eq!(puzzle.bin_name(), "2015-03");
+        assert_eq!(PuzzleId::from_bin_name("2015-03"), Some(puzzle));
+        assert_eq!(puzzle.bin_path(), "./src/bin/2015-03.rs");
+        assert_eq!(
+            puzzle.input_path().to_string_lossy(),
+            "data/2015/inputs/03.txt"
+        );
+        assert_eq!(
+            puzzle.puzzle_path().to_string_lossy(),
+            "data/2015/puzzles/03.md"
+        );
+    }
+
+    #[test]
+    fn builds_paths_for_primary_year() {
+        let puzzle = PuzzleId::primary(day!(3));
+        assert_eq!(puzzle.bin_name(), "03");
+        assert_eq!(PuzzleId::from_bin_name("03"), Some(puzzle));
+        assert_eq!(PuzzleId::from_bin_name("advent_of_code"), None);
+        assert_eq!(
+            puzzle.example_path().to_string_lossy(),
+            "data/examples/03.txt"
+        );
+    }
+}